@@ -0,0 +1,7 @@
+-- name: chassi_loc
+-- description: Localização dos chassis, limitada às primeiras linhas
+-- param: limite int = 1000000
+-- cache_ttl: 60
+SELECT *
+FROM SYSADM.PS_MMC_CHASSI_LOC
+WHERE ROWNUM <= :1
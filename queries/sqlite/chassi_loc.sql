@@ -0,0 +1,5 @@
+-- name: chassi_loc
+-- description: Localização dos chassis, limitada às primeiras linhas (SQLite local)
+-- param: limite int = 1000000
+-- cache_ttl: 60
+SELECT * FROM PS_MMC_CHASSI_LOC LIMIT :1
@@ -14,6 +14,7 @@ use serde::Serialize;
 use utils::polars_df_to_json::df_to_json_each_column;
 use utils::polars_df_to_json::df_to_json_each_row; // o enum acima
 mod actix;
+mod oracle;
 mod utils;
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use actix::api_errors::ApiError;
+use actix_web::get;
+use actix_web::web;
+use actix_web::App;
+use actix_web::HttpResponse;
+use actix_web::HttpServer;
+use actix_web::Result;
+use oracle::connection::EngineOracle;
+use oracle::options::QueryOptions;
+use utils::polars_df_to_json::df_to_json_each_column;
+
+mod actix;
+mod oracle;
+mod utils;
+
+// O fetch roda fora das threads do actix; se o cliente desconectar, a leitura é interrompida
+#[get("/df")]
+async fn get_df(engine: web::Data<Arc<EngineOracle>>) -> Result<HttpResponse, ApiError>
+{
+    let sql = r#"
+        SELECT *
+        FROM SYSADM.PS_MMC_CHASSI_LOC
+        WHERE ROWNUM <= :1
+    "#;
+
+    let df = engine
+        .get_ref()
+        .clone()
+        .query_to_polars_df_async(sql, vec![Box::new(500000)], QueryOptions::default())
+        .await?;
+
+    let json = df_to_json_each_column(&df)?.to_string();
+    Ok(HttpResponse::Ok().content_type("application/json").body(json))
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()>
+{
+    // 1) .env + logger
+    dotenv::dotenv().ok();
+    env_logger::init();
+
+    // 2) Instancia o EngineOracle e compartilha entre os workers
+    let engine = Arc::new(EngineOracle::new().expect("falha ao conectar no Oracle"));
+
+    HttpServer::new(move || App::new().app_data(web::Data::new(engine.clone())).service(get_df))
+        .workers(4)
+        .bind("127.0.0.1:8080")?
+        .run()
+        .await
+}
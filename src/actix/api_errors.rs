@@ -1,11 +1,13 @@
+use actix_web::error::HttpError;
 use actix_web::http::StatusCode;
 use actix_web::HttpResponse;
 use actix_web::ResponseError;
-use http::Error as HttpError;
 use polars::prelude::PolarsError;
 use serde_json::Error as SerdeError;
 use thiserror::Error; // importar o http::Error
 
+use crate::oracle::error::EngineError;
+
 #[derive(Error, Debug)]
 pub enum ApiError
 {
@@ -17,6 +19,9 @@ pub enum ApiError
 
     #[error("Falha ao construir resposta HTTP: {0}")]
     Http(#[from] HttpError), // adiciona o From<HttpError>
+
+    #[error("{0}")]
+    Engine(#[from] EngineError),
 }
 
 impl ResponseError for ApiError
@@ -28,6 +33,7 @@ impl ResponseError for ApiError
             ApiError::DataFrame(_) => StatusCode::UNPROCESSABLE_ENTITY,
             ApiError::Json(_) => StatusCode::BAD_REQUEST,
             ApiError::Http(_) => StatusCode::INTERNAL_SERVER_ERROR, // ou outro código adequado
+            ApiError::Engine(ref e) => StatusCode::from_u16(e.http_status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
         }
     }
 
@@ -16,6 +16,7 @@ use utils::polars_df_to_json::df_to_json_each_column;
 use utils::polars_df_to_json::df_to_json_each_row;
 
 mod axum_examples;
+mod oracle;
 mod utils;
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -1,16 +1,33 @@
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::body::Body;
 use axum::extract::Extension;
+use axum::extract::Json;
+use axum::extract::Path;
+use axum::extract::Query;
 use axum::http::header;
 use axum::http::StatusCode;
 use axum::response::Response;
 use axum::routing::get;
 use axum::Router;
 use axum_examples::api_errors::AppError;
+use oracle::cache::CacheConfig;
+use oracle::cache::CachedEngine;
 use oracle::connection::EngineOracle;
+use oracle::engine::query_df_async;
+use oracle::engine::SqlEngine;
+use oracle::engine::SqlValue;
+use oracle::error::EngineError;
+use oracle::options::QueryOptions;
+use oracle::registry::QueryRegistry;
+use oracle::sqlite::EngineSqlite;
+use serde_json::json;
 use serde_json::Value as JsonValue;
 use tokio::net::TcpListener;
+use tokio::task;
+use tokio::time;
 use utils::polars_df_to_json::df_to_json_each_column;
 
 mod axum_examples;
@@ -22,12 +39,53 @@ async fn main() -> Result<(), std::io::Error>
 {
     // 1) .env + logger
     dotenv::dotenv().ok();
+    env_logger::init();
 
-    // 2) Instancia o EngineOracle e embala num Arc
-    let engine = Arc::new(EngineOracle::new().expect("falha ao conectar no Oracle"));
+    // 2) Usa um SQLite local quando SQLITE_PATH estiver definida; caso contrário, o Oracle
+    let backend: Arc<dyn SqlEngine> = match std::env::var("SQLITE_PATH")
+    {
+        Ok(path) => Arc::new(EngineSqlite::open(path).expect("falha ao abrir o SQLite")),
+        Err(_) => Arc::new(EngineOracle::new().expect("falha ao conectar no Oracle")),
+    };
 
-    // 3) Cria o Router e injeta o Arc<EngineOracle> como camada de estado
-    let app = Router::new().route("/df", get(get_df)).layer(Extension(engine));
+    // Resultados repetidos do /df saem do cache por até 60s
+    let cached = Arc::new(CachedEngine::new(backend, CacheConfig::default()));
+    let engine: Arc<dyn SqlEngine> = cached.clone();
+
+    // As consultas vêm de queries/*.sql (queries/sqlite no SQLite) e são recarregadas quando mudam
+    let queries_dir = match engine.backend_name()
+    {
+        "sqlite" => "queries/sqlite",
+        _ => "queries",
+    };
+    let registry = Arc::new(QueryRegistry::load(queries_dir).expect("falha ao carregar as consultas"));
+    let watched = registry.clone();
+    tokio::spawn(async move {
+        let mut interval = time::interval(Duration::from_secs(5));
+        loop
+        {
+            interval.tick().await;
+            let registry = watched.clone();
+            match task::spawn_blocking(move || registry.reload_if_changed()).await
+            {
+                Ok(Err(e)) => log::warn!("Consultas não recarregadas; mantendo a versão anterior: {}", e),
+                Err(e) => log::warn!("Falha na recarga das consultas: {}", e),
+                Ok(Ok(_)) =>
+                {},
+            }
+        }
+    });
+
+    // 3) Cria o Router e injeta o Arc<dyn SqlEngine> como camada de estado
+    let app = Router::new()
+        .route("/df", get(get_df))
+        .route("/queries/{name}", get(get_named_query))
+        .route("/health", get(get_health))
+        .route("/pool-stats", get(get_pool_stats))
+        .route("/cache-stats", get(get_cache_stats))
+        .layer(Extension(engine))
+        .layer(Extension(cached))
+        .layer(Extension(registry));
 
     // 4) Sobe o servidor
     let listener = TcpListener::bind("127.0.0.1:8080").await?;
@@ -36,19 +94,49 @@ async fn main() -> Result<(), std::io::Error>
     Ok(())
 }
 
-// Agora o handler recebe o Arc<EngineOracle> como parâmetro
-async fn get_df(Extension(engine): Extension<Arc<EngineOracle>>) -> Result<Response, AppError>
+// O handler depende só do trait, então roda igual contra Oracle ou SQLite
+async fn get_df(
+    Extension(engine): Extension<Arc<dyn SqlEngine>>,
+    Extension(registry): Extension<Arc<QueryRegistry>>,
+) -> Result<Response, AppError>
+{
+    let query = registry.bind("chassi_loc", &[("limite", SqlValue::from(500000))])?;
+
+    // usa a mesma instância que veio de main, com o fetch fora dos workers do runtime;
+    // passados 30s a consulta é interrompida no Oracle e o handler responde 504
+    let options = QueryOptions {
+        timeout: Some(Duration::from_secs(30)),
+        ..query.options
+    };
+    let df = query_df_async(engine, query.sql, query.params, options).await?;
+
+    json_response(&df)
+}
+
+// Executa qualquer consulta do registro; a query string vira os argumentos, validados
+// contra o cabeçalho do arquivo (400 se inválidos, 404 se a consulta não existir)
+async fn get_named_query(
+    Extension(engine): Extension<Arc<dyn SqlEngine>>,
+    Extension(registry): Extension<Arc<QueryRegistry>>,
+    Path(name): Path<String>,
+    Query(args): Query<HashMap<String, String>>,
+) -> Result<Response, AppError>
 {
-    let sql = r#"
-        SELECT *
-        FROM SYSADM.PS_MMC_CHASSI_LOC
-        WHERE ROWNUM <= :1
-    "#;
+    let args: Vec<(&str, SqlValue)> = args.iter().map(|(k, v)| (k.as_str(), SqlValue::from(v.as_str()))).collect();
+    let query = registry.bind(&name, &args)?;
 
-    // usa a mesma instância que veio de main
-    let df = engine.query_to_polars_df(sql, &[&500000])?;
+    let options = QueryOptions {
+        timeout: Some(Duration::from_secs(30)),
+        ..query.options
+    };
+    let df = query_df_async(engine, query.sql, query.params, options).await?;
 
-    let data_json: JsonValue = df_to_json_each_column(&df)?;
+    json_response(&df)
+}
+
+fn json_response(df: &polars::prelude::DataFrame) -> Result<Response, AppError>
+{
+    let data_json: JsonValue = df_to_json_each_column(df)?;
     let body_str = serde_json::to_string(&data_json)?;
 
     let response = Response::builder()
@@ -57,3 +145,33 @@ async fn get_df(Extension(engine): Extension<Arc<EngineOracle>>) -> Result<Respo
         .body(Body::from(body_str))?;
     Ok(response)
 }
+
+// Health check: um ping no banco (reconectando se a conexão tiver caído) e a latência medida
+async fn get_health(Extension(engine): Extension<Arc<dyn SqlEngine>>) -> Result<Json<JsonValue>, AppError>
+{
+    let backend = engine.backend_name();
+    let latency = task::spawn_blocking(move || engine.ping())
+        .await
+        .map_err(EngineError::from)??;
+    Ok(Json(json!({
+        "status": "ok",
+        "backend": backend,
+        "latency_ms": latency.as_secs_f64() * 1000.0,
+    })))
+}
+
+// Expõe o uso do pool para acompanhar saturação (conexão única retorna `null`)
+async fn get_pool_stats(Extension(engine): Extension<Arc<dyn SqlEngine>>) -> Result<Json<JsonValue>, AppError>
+{
+    let stats = engine.pool_stats()?;
+    let saturation = stats.as_ref().map(|s| s.saturation());
+    Ok(Json(json!({ "pool": stats, "saturation": saturation })))
+}
+
+// Hits, misses e memória do cache, para ajustar TTL e orçamento
+async fn get_cache_stats(Extension(cached): Extension<Arc<CachedEngine>>) -> Json<JsonValue>
+{
+    let stats = cached.cache().stats();
+    let hit_ratio = stats.hit_ratio();
+    Json(json!({ "cache": stats, "hit_ratio": hit_ratio }))
+}
@@ -8,6 +8,8 @@ use polars::prelude::PolarsError;
 use serde_json::json;
 use thiserror::Error;
 
+use crate::oracle::error::EngineError;
+
 #[derive(Debug, Error)]
 pub enum AppError
 {
@@ -23,6 +25,9 @@ pub enum AppError
     #[error("Erro no Oracle: {0}")]
     Oracle(#[from] oracle::Error),
 
+    #[error("{0}")]
+    Engine(#[from] EngineError),
+
     /// captura qualquer `Box<dyn Error + Send + Sync>`
     #[error("Erro genérico: {0}")]
     Generic(#[from] Box<dyn std::error::Error + Send + Sync>),
@@ -37,6 +42,10 @@ impl IntoResponse for AppError
             AppError::Json(e) => (StatusCode::BAD_REQUEST, e.to_string()),
             AppError::Http(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
             AppError::Oracle(e) => (StatusCode::BAD_GATEWAY, e.to_string()),
+            AppError::Engine(e) => (
+                StatusCode::from_u16(e.http_status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                e.to_string(),
+            ),
             AppError::Generic(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
         };
 
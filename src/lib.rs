@@ -0,0 +1,5 @@
+//! Módulos compartilhados pelos exemplos: o motor Oracle/SQLite (`oracle`) e os utilitários de
+//! conversão de DataFrames (`utils`).
+
+pub mod oracle;
+pub mod utils;
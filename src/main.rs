@@ -1,31 +1,267 @@
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
-use oracle::connection::EngineOracle;
+use clap::ArgGroup;
+use clap::Parser;
+use clap::ValueEnum;
+use indicatif::ProgressBar;
+use indicatif::ProgressStyle;
 use polars::prelude::*;
-mod oracle;
-mod utils;
+use rust_concepts::oracle::connection::EngineOracle;
+use rust_concepts::oracle::engine::SqlEngine;
+use rust_concepts::oracle::engine::SqlValue;
+use rust_concepts::oracle::export::export_query;
+use rust_concepts::oracle::export::parse_param;
+use rust_concepts::oracle::export::ExportFormat;
+use rust_concepts::oracle::export::ExportOptions;
+use rust_concepts::oracle::options::QueryOptions;
+use rust_concepts::oracle::registry::QueryRegistry;
+use rust_concepts::oracle::sqlite::EngineSqlite;
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>>
+/// Exporta o resultado de uma consulta para Parquet, Arrow IPC, CSV ou NDJSON, em blocos.
+#[derive(Parser, Debug)]
+#[command(version, about)]
+#[command(group(ArgGroup::new("source").required(true).args(["sql", "sql_file", "query"])))]
+struct Args
 {
-    // 1) .env + logger
+    /// Texto da consulta
+    #[arg(long)]
+    sql: Option<String>,
+
+    /// Arquivo .sql com a consulta
+    #[arg(long)]
+    sql_file: Option<PathBuf>,
+
+    /// Consulta nomeada do registro (arquivos .sql de --queries-dir)
+    #[arg(long)]
+    query: Option<String>,
+
+    /// Diretório do registro de consultas [padrão: queries, ou queries/sqlite com SQLite]
+    #[arg(long, requires = "query")]
+    queries_dir: Option<PathBuf>,
+
+    /// Argumento da consulta nomeada, como nome=valor (convertido para o tipo declarado no arquivo)
+    #[arg(short, long = "arg", requires = "query", value_parser = parse_named_arg)]
+    args: Vec<(String, SqlValue)>,
+
+    /// Bind posicional (:1, :2, ...), na ordem. Aceita prefixo int:, float:, text:, bool:, date:, datetime: ou null
+    #[arg(short, long = "param", conflicts_with = "query", value_parser = parse_param)]
+    params: Vec<SqlValue>,
+
+    /// Arquivo de saída
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// Formato de saída; por padrão, deduzido pela extensão do arquivo
+    #[arg(short, long, value_enum)]
+    format: Option<Format>,
+
+    /// Compressão do Parquet
+    #[arg(long, value_enum, default_value_t = Compression::Zstd)]
+    compression: Compression,
+
+    /// Separador do CSV
+    #[arg(long, default_value_t = ',')]
+    delimiter: char,
+
+    /// Caractere de aspas do CSV
+    #[arg(long, default_value_t = '"')]
+    quote: char,
+
+    /// Não grava a linha de cabeçalho no CSV
+    #[arg(long)]
+    no_header: bool,
+
+    /// Linhas por bloco lido e gravado
+    #[arg(long, default_value_t = 100_000)]
+    chunk_rows: usize,
+
+    /// Linhas buscadas por round-trip ao Oracle
+    #[arg(long, default_value_t = 10_000)]
+    fetch_array_size: u32,
+
+    /// Usa o banco SQLite informado em vez do Oracle
+    #[arg(long, env = "SQLITE_PATH")]
+    sqlite: Option<PathBuf>,
+
+    /// Não mostra o progresso
+    #[arg(long)]
+    no_progress: bool,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Format
+{
+    Parquet,
+    Ipc,
+    Csv,
+    Ndjson,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Compression
+{
+    Uncompressed,
+    Snappy,
+    Gzip,
+    Lz4,
+    Zstd,
+    Brotli,
+}
+
+impl From<Compression> for ParquetCompression
+{
+    fn from(compression: Compression) -> Self
+    {
+        match compression
+        {
+            Compression::Uncompressed => ParquetCompression::Uncompressed,
+            Compression::Snappy => ParquetCompression::Snappy,
+            Compression::Gzip => ParquetCompression::Gzip(None),
+            Compression::Lz4 => ParquetCompression::Lz4Raw,
+            Compression::Zstd => ParquetCompression::Zstd(None),
+            Compression::Brotli => ParquetCompression::Brotli(None),
+        }
+    }
+}
+
+impl Args
+{
+    /// SQL e binds a executar: o texto informado ou a consulta nomeada, com os argumentos validados.
+    fn statement(&self, backend: &str) -> Result<(String, Vec<SqlValue>), Box<dyn std::error::Error>>
+    {
+        if let Some(name) = &self.query
+        {
+            let dir = match (&self.queries_dir, backend)
+            {
+                (Some(dir), _) => dir.clone(),
+                (None, "sqlite") => PathBuf::from("queries/sqlite"),
+                (None, _) => PathBuf::from("queries"),
+            };
+            let registry = QueryRegistry::load(dir)?;
+            let args: Vec<(&str, SqlValue)> = self.args.iter().map(|(k, v)| (k.as_str(), v.clone())).collect();
+            let query = registry.bind(name, &args)?;
+            return Ok((query.sql, query.params));
+        }
+
+        let sql = match (&self.sql, &self.sql_file)
+        {
+            (Some(sql), _) => sql.clone(),
+            (None, Some(path)) =>
+            {
+                let text = std::fs::read_to_string(path).map_err(|e| format!("falha ao ler {}: {}", path.display(), e))?;
+                // O `;` final é do SQL*Plus; enviado ao driver, causa ORA-00911
+                text.trim().trim_end_matches(';').trim_end().to_string()
+            },
+            (None, None) => return Err("informe --sql, --sql-file ou --query".into()),
+        };
+        Ok((sql, self.params.clone()))
+    }
+
+    fn export_format(&self) -> Result<ExportFormat, Box<dyn std::error::Error>>
+    {
+        let format = match self.format
+        {
+            Some(format) => format,
+            None => match ExportFormat::from_path(&self.output)
+            {
+                Some(ExportFormat::Parquet { .. }) => Format::Parquet,
+                Some(ExportFormat::Ipc) => Format::Ipc,
+                Some(ExportFormat::Csv { .. }) => Format::Csv,
+                Some(ExportFormat::Ndjson) => Format::Ndjson,
+                None =>
+                {
+                    return Err(format!(
+                        "não foi possível deduzir o formato de {}; use --format",
+                        self.output.display()
+                    )
+                    .into())
+                },
+            },
+        };
+
+        Ok(match format
+        {
+            Format::Parquet => ExportFormat::Parquet {
+                compression: self.compression.into(),
+            },
+            Format::Ipc => ExportFormat::Ipc,
+            Format::Csv => ExportFormat::Csv {
+                separator: ascii_byte(self.delimiter, "--delimiter")?,
+                quote: ascii_byte(self.quote, "--quote")?,
+                header: !self.no_header,
+            },
+            Format::Ndjson => ExportFormat::Ndjson,
+        })
+    }
+}
+
+/// Lê `nome=valor`; o valor segue como texto e é convertido pelo tipo declarado na consulta.
+fn parse_named_arg(text: &str) -> Result<(String, SqlValue), String>
+{
+    let (name, value) = text
+        .split_once('=')
+        .ok_or_else(|| format!("argumento {:?} inválido; use nome=valor", text))?;
+    let value = if value.eq_ignore_ascii_case("null")
+    {
+        SqlValue::Null
+    }
+    else
+    {
+        SqlValue::Text(value.to_string())
+    };
+    Ok((name.trim().to_string(), value))
+}
+
+fn ascii_byte(c: char, flag: &str) -> Result<u8, Box<dyn std::error::Error>>
+{
+    u8::try_from(c)
+        .ok()
+        .filter(u8::is_ascii)
+        .ok_or_else(|| format!("{} precisa ser um caractere ASCII", flag).into())
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>>
+{
+    // 1) .env + logger + argumentos
     dotenv::dotenv().ok();
+    env_logger::init();
+    let args = Args::parse();
 
-    // 2) Instancia o EngineOracle e embala num Arc
-    let engine = Arc::new(EngineOracle::new().expect("falha ao conectar no Oracle"));
+    let mut options = ExportOptions::new(args.export_format()?);
+    options.chunk_rows = args.chunk_rows;
+    options.query = QueryOptions::default()
+        .fetch_array_size(args.fetch_array_size)
+        .prefetch_rows(args.fetch_array_size);
 
-    let sql = r#"
-        SELECT *
-        FROM SYSADM.PS_MMC_CHASSI_LOC
-        WHERE ROWNUM <= :1
-    "#;
+    // 2) Usa o SQLite quando --sqlite (ou SQLITE_PATH) for informado; caso contrário, o Oracle
+    let engine: Arc<dyn SqlEngine> = match &args.sqlite
+    {
+        Some(path) => Arc::new(EngineSqlite::open(path)?),
+        None => Arc::new(EngineOracle::new()?),
+    };
+    let (sql, params) = args.statement(engine.backend_name())?;
 
-    let mut df: DataFrame = engine.query_to_polars_df(sql, &[&1000000]).unwrap();
+    // 3) Grava bloco a bloco, mostrando as linhas já exportadas
+    let progress = if args.no_progress
+    {
+        ProgressBar::hidden()
+    }
+    else
+    {
+        ProgressBar::new_spinner()
+    };
+    progress.set_style(ProgressStyle::with_template(
+        "{spinner} {human_pos} linhas gravadas ({per_sec}) [{elapsed}]",
+    )?);
+    progress.enable_steady_tick(Duration::from_millis(200));
 
-    let path = std::env::current_dir().unwrap();
-    let mut file = std::fs::File::create(path.join("src/data/test.csv")).unwrap();
-    CsvWriter::new(&mut file).finish(&mut df).unwrap();
+    let rows = export_query(engine.as_ref(), &sql, &params, &args.output, &options, |rows| {
+        progress.set_position(rows)
+    });
+    progress.finish_and_clear();
 
-    println!("{}", df);
+    println!("{} linhas exportadas para {}", rows?, args.output.display());
     Ok(())
 }
@@ -1,18 +1,19 @@
-use std::sync::Arc;
+use std::error::Error;
+use std::path::Path;
 
-use oracle::connection::EngineOracle;
-use polars::prelude::*;
-mod oracle;
-mod utils;
+use dotenv::dotenv;
+use rust_concepts::oracle::connection::EngineOracle;
+use rust_concepts::oracle::engine::SqlValue;
+use rust_concepts::oracle::export::export_query;
+use rust_concepts::oracle::export::ExportFormat;
+use rust_concepts::oracle::export::ExportOptions;
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>>
+fn main() -> Result<(), Box<dyn Error>>
 {
-    // 1) .env + logger
-    dotenv::dotenv().ok();
+    dotenv().ok();
+    env_logger::init();
 
-    // 2) Instancia o EngineOracle e embala num Arc
-    let engine = Arc::new(EngineOracle::new().expect("falha ao conectar no Oracle"));
+    let engine = EngineOracle::new()?;
 
     let sql = r#"
         SELECT *
@@ -20,12 +21,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>>
         WHERE ROWNUM <= :1
     "#;
 
-    let mut df: DataFrame = engine.query_to_polars_df(sql, &[&1000000]).unwrap();
+    // Grava em blocos, sem montar o resultado inteiro em memória. A CLI completa (Parquet,
+    // IPC, NDJSON, --sql-file, --param) é o src/main.rs
+    let path = Path::new("src/data/test.csv");
+    let options = ExportOptions::new(ExportFormat::from_path(path).ok_or("extensão desconhecida")?);
+    let rows = export_query(&engine, sql, &[SqlValue::Int(1_000_000)], path, &options, |_| {})?;
 
-    let path = std::env::current_dir().unwrap();
-    let mut file = std::fs::File::create(path.join("src/data/test.csv")).unwrap();
-    CsvWriter::new(&mut file).finish(&mut df).unwrap();
-
-    println!("{}", df);
+    println!("{} linhas gravadas em {}", rows, path.display());
     Ok(())
 }
@@ -0,0 +1,78 @@
+use std::env;
+use std::error::Error;
+use std::path::Path;
+
+use calamine::open_workbook;
+use calamine::Reader;
+use calamine::Xlsx;
+use dotenv::dotenv;
+use log::info;
+use polars::prelude::*;
+use rust_concepts::oracle::connection::EngineOracle;
+use rust_concepts::oracle::ddl::DdlOptions;
+use rust_concepts::oracle::write::WriteMode;
+
+fn read_excel_to_dataframe<P: AsRef<Path>>(path: P) -> Result<DataFrame, Box<dyn Error>>
+{
+    let mut workbook: Xlsx<_> = open_workbook(path)?;
+    let range = workbook.worksheet_range_at(0).ok_or("Planilha não encontrada")??;
+
+    let mut df = DataFrame::default();
+
+    // A primeira linha contém os cabeçalhos
+    if range.rows().count() > 0
+    {
+        let mut columns: Vec<Series> = Vec::new();
+
+        for (i, header) in range.rows().next().unwrap().iter().enumerate()
+        {
+            let header = header.to_string();
+            let values: Vec<Option<String>> = range
+                .rows()
+                .skip(1)
+                .map(|row| row.get(i).map(|cell| cell.to_string()))
+                .collect();
+
+            columns.push(Series::new(header.into(), values));
+        }
+
+        df = DataFrame::new(columns.into_iter().map(|s| s.into()).collect())?;
+    }
+
+    Ok(df)
+}
+
+fn main() -> Result<(), Box<dyn Error>>
+{
+    dotenv().ok();
+    env_logger::init();
+
+    let home_path = env::var("HOME")?;
+    let file_path =
+        Path::new(&home_path).join("code/exclude-from-compact/rust-concepts/src/data/Cripple Detalhado por Chassi.xlsx");
+
+    let df = read_excel_to_dataframe(file_path)?;
+    info!("Excel lido: {} linhas, colunas {:?}", df.height(), df.get_column_names());
+
+    let engine = EngineOracle::new()?;
+
+    // Cria a tabela na primeira carga e amplia as colunas quando o Excel trouxer textos maiores
+    let drift = engine.sync_table("CRIPPLE_CHASSI", &df, &DdlOptions::new().primary_key(["Chassi"]))?;
+    info!(
+        "{} instruções DDL aplicadas; conflitos: {:?}",
+        drift.statements.len(),
+        drift.conflicts
+    );
+
+    // As colunas do Excel chegam como String; o Oracle converte para o tipo da coluna de destino
+    let rows = engine.write_polars_df(
+        "CRIPPLE_CHASSI",
+        &df,
+        WriteMode::Upsert {
+            keys: vec!["Chassi".to_string()],
+        },
+    )?;
+    println!("{} linhas gravadas", rows);
+
+    Ok(())
+}
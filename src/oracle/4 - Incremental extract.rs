@@ -0,0 +1,31 @@
+use std::error::Error;
+
+use dotenv::dotenv;
+use rust_concepts::oracle::connection::EngineOracle;
+use rust_concepts::oracle::incremental::IncrementalExtract;
+use rust_concepts::oracle::incremental::Watermark;
+
+fn main() -> Result<(), Box<dyn Error>>
+{
+    dotenv().ok();
+    env_logger::init();
+
+    let engine = EngineOracle::new()?;
+
+    // Cada execução grava só as linhas alteradas desde a anterior em um novo Parquet;
+    // a marca d'água fica em src/data/incremental/_watermarks.json
+    let extract = IncrementalExtract::new(
+        "SYSADM.PS_MMC_CHASSI_LOC",
+        Watermark::Column("LASTUPDDTTM".to_string()),
+        "src/data/incremental",
+    );
+
+    let report = extract.run(&engine)?;
+    match report.file
+    {
+        Some(file) => println!("{} linhas gravadas em {}", report.rows, file.display()),
+        None => println!("Nenhuma linha nova (marca d'água: {:?})", report.high),
+    }
+
+    Ok(())
+}
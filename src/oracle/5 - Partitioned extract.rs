@@ -0,0 +1,38 @@
+use std::error::Error;
+use std::path::Path;
+
+use dotenv::dotenv;
+use rust_concepts::oracle::config::OracleConfig;
+use rust_concepts::oracle::connection::EngineOracle;
+use rust_concepts::oracle::partition::PartitionStrategy;
+use rust_concepts::oracle::partition::PartitionedExtract;
+use rust_concepts::oracle::pool::PoolConfig;
+
+fn main() -> Result<(), Box<dyn Error>>
+{
+    dotenv().ok();
+    env_logger::init();
+
+    // Uma conexão por partição lida ao mesmo tempo
+    let mut config = OracleConfig::from_env()?;
+    config.pool = Some(PoolConfig {
+        max_connections: 8,
+        ..config.pool.unwrap_or_default()
+    });
+    let engine = EngineOracle::from_config(&config)?;
+
+    // 8 faixas de ROWID, lidas em paralelo e gravadas em um Parquet por partição
+    let extract = PartitionedExtract::new("SYSADM.PS_MMC_CHASSI_LOC", PartitionStrategy::RowidRange, 8);
+    let report = extract.to_parquet(&engine, Path::new("src/data/partitions"))?;
+
+    for partition in &report.partitions
+    {
+        println!(
+            "partição {}: {} linhas em {:?} ({})",
+            partition.index, partition.rows, partition.elapsed, partition.predicate
+        );
+    }
+    println!("{} linhas em {:?}", report.rows, report.elapsed);
+
+    Ok(())
+}
@@ -0,0 +1,552 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+use std::time::Duration;
+use std::time::Instant;
+
+use log::debug;
+use polars::prelude::DataFrame;
+use serde::Serialize;
+
+use super::cancel::CancelToken;
+use super::engine::DataFrameChunks;
+use super::engine::SqlEngine;
+use super::engine::SqlValue;
+use super::error::EngineResult;
+use super::guard::find;
+use super::guard::skip_q_string;
+use super::guard::skip_string;
+use super::options::QueryOptions;
+use super::pool::PoolStats;
+
+/// Parâmetros do cache de resultados.
+#[derive(Debug, Clone)]
+pub struct CacheConfig
+{
+    /// Validade das entradas quando a consulta não define `QueryOptions::cache_ttl`.
+    pub default_ttl: Duration,
+    /// Memória máxima ocupada pelos DataFrames em cache; ao estourar, sai o menos usado.
+    pub max_bytes: usize,
+}
+
+impl Default for CacheConfig
+{
+    fn default() -> Self
+    {
+        Self {
+            default_ttl: Duration::from_secs(60),
+            max_bytes: 256 * 1024 * 1024,
+        }
+    }
+}
+
+/// Contadores do cache, expostos para ajustar TTL e orçamento de memória.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CacheStats
+{
+    pub hits: u64,
+    pub misses: u64,
+    /// Entradas removidas para liberar memória (expiradas e invalidadas não contam).
+    pub evictions: u64,
+    pub entries: usize,
+    pub bytes: usize,
+    pub max_bytes: usize,
+}
+
+impl CacheStats
+{
+    /// Fração das consultas atendidas pelo cache (0.0 a 1.0).
+    pub fn hit_ratio(&self) -> f64
+    {
+        let total = self.hits + self.misses;
+        if total == 0
+        {
+            0.0
+        }
+        else
+        {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+struct Entry
+{
+    df: DataFrame,
+    bytes: usize,
+    expires_at: Instant,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct CacheState
+{
+    entries: HashMap<String, Entry>,
+    /// Ordem de uso: o menor tick é o menos usado recentemente.
+    lru: BTreeMap<u64, String>,
+    tick: u64,
+    stats: CacheStats,
+}
+
+impl CacheState
+{
+    fn touch(&mut self, key: &str)
+    {
+        self.tick += 1;
+        if let Some(entry) = self.entries.get_mut(key)
+        {
+            self.lru.remove(&entry.last_used);
+            entry.last_used = self.tick;
+            self.lru.insert(self.tick, key.to_string());
+        }
+    }
+
+    fn remove(&mut self, key: &str) -> bool
+    {
+        match self.entries.remove(key)
+        {
+            Some(entry) =>
+            {
+                self.lru.remove(&entry.last_used);
+                self.stats.bytes -= entry.bytes;
+                true
+            },
+            None => false,
+        }
+    }
+}
+
+/// Cache de DataFrames em memória, com TTL por entrada e descarte LRU por orçamento de memória.
+///
+/// As chaves vêm de `cache_key`: o SQL normalizado seguido dos binds, o que permite invalidar
+/// todas as variações de uma consulta pelo prefixo.
+pub struct QueryCache
+{
+    config: CacheConfig,
+    state: Mutex<CacheState>,
+}
+
+impl QueryCache
+{
+    pub fn new(config: CacheConfig) -> Self
+    {
+        let state = CacheState {
+            stats: CacheStats {
+                max_bytes: config.max_bytes,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        Self {
+            config,
+            state: Mutex::new(state),
+        }
+    }
+
+    /// Busca uma entrada válida, registrando hit ou miss.
+    pub fn get(&self, key: &str) -> Option<DataFrame>
+    {
+        let mut state = self.lock();
+        let expired = state.entries.get(key).map(|e| e.expires_at <= Instant::now());
+
+        match expired
+        {
+            Some(false) =>
+            {
+                state.stats.hits += 1;
+                state.touch(key);
+                state.entries.get(key).map(|e| e.df.clone())
+            },
+            Some(true) =>
+            {
+                state.remove(key);
+                state.stats.misses += 1;
+                None
+            },
+            None =>
+            {
+                state.stats.misses += 1;
+                None
+            },
+        }
+    }
+
+    /// Guarda o DataFrame por `ttl`, descartando as entradas menos usadas se faltar memória.
+    ///
+    /// DataFrames maiores que o orçamento inteiro não são guardados.
+    pub fn insert(&self, key: impl Into<String>, df: DataFrame, ttl: Duration)
+    {
+        let key = key.into();
+        let bytes = df.estimated_size();
+        if bytes > self.config.max_bytes || ttl.is_zero()
+        {
+            debug!("Resultado não guardado no cache ({} bytes): {}", bytes, key);
+            return;
+        }
+
+        let mut state = self.lock();
+        state.remove(&key);
+
+        while state.stats.bytes + bytes > self.config.max_bytes
+        {
+            let Some((_, oldest)) = state.lru.pop_first()
+            else
+            {
+                break;
+            };
+            state.remove(&oldest);
+            state.stats.evictions += 1;
+        }
+
+        state.entries.insert(
+            key.clone(),
+            Entry {
+                df,
+                bytes,
+                expires_at: Instant::now() + ttl,
+                last_used: 0,
+            },
+        );
+        state.stats.bytes += bytes;
+        state.touch(&key);
+    }
+
+    /// Remove a entrada com a chave exata. Retorna se ela existia.
+    pub fn invalidate(&self, key: &str) -> bool
+    {
+        self.lock().remove(key)
+    }
+
+    /// Remove as entradas cuja chave começa com `prefix` (normalizado como SQL). Retorna quantas saíram.
+    ///
+    /// `invalidate_prefix("SELECT * FROM SYSADM.PS_MMC_CHASSI_LOC")` remove a consulta com
+    /// qualquer valor de bind.
+    pub fn invalidate_prefix(&self, prefix: &str) -> usize
+    {
+        let prefix = normalize_sql(prefix);
+        let mut state = self.lock();
+        let keys: Vec<String> = state.entries.keys().filter(|k| k.starts_with(&prefix)).cloned().collect();
+        for key in &keys
+        {
+            state.remove(key);
+        }
+        keys.len()
+    }
+
+    /// Esvazia o cache, mantendo os contadores de hit e miss.
+    pub fn clear(&self)
+    {
+        let mut state = self.lock();
+        state.entries.clear();
+        state.lru.clear();
+        state.stats.bytes = 0;
+    }
+
+    pub fn stats(&self) -> CacheStats
+    {
+        let state = self.lock();
+        CacheStats {
+            entries: state.entries.len(),
+            ..state.stats.clone()
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CacheState>
+    {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Chave de cache de uma consulta: SQL normalizado, binds e opções que mudam o DataFrame gerado.
+pub fn cache_key(sql: &str, params: &[SqlValue], options: &QueryOptions) -> String
+{
+    format!(
+        "{} -- {:?} {:?} {:?} {:?} {:?} {:?} {:?}",
+        normalize_sql(sql),
+        params,
+        options.mode,
+        options.date_mapping,
+        options.boolean_columns,
+        options.lob_mode,
+        options.lob_columns,
+        options.column_types
+    )
+}
+
+/// Colapsa espaços e quebras de linha e remove o `;` final, para que a indentação não mude a chave.
+///
+/// Literais de texto, identificadores entre aspas e comentários são mantidos como escritos:
+/// `'a  b'` e `'a b'` são consultas diferentes. Um literal não encerrado é copiado até o fim.
+pub fn normalize_sql(sql: &str) -> String
+{
+    let chars: Vec<char> = sql.chars().collect();
+    let mut normalized = String::with_capacity(sql.len());
+    let mut space = false;
+    let mut i = 0;
+    while i < chars.len()
+    {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if c.is_whitespace()
+        {
+            space = true;
+            i += 1;
+            continue;
+        }
+
+        let end = if c == '-' && next == Some('-')
+        {
+            find(&chars, i, &['\n']).map_or(chars.len(), |end| end + 1)
+        }
+        else if c == '/' && next == Some('*')
+        {
+            find(&chars, i + 2, &['*', '/']).map_or(chars.len(), |end| end + 2)
+        }
+        else if c == '\''
+        {
+            skip_string(&chars, i).unwrap_or(chars.len())
+        }
+        else if matches!(c, 'q' | 'Q') && next == Some('\'')
+        {
+            skip_q_string(&chars, i + 1).unwrap_or(chars.len())
+        }
+        else if matches!(c, 'n' | 'N') && next == Some('\'')
+        {
+            skip_string(&chars, i + 1).unwrap_or(chars.len())
+        }
+        else if c == '"'
+        {
+            find(&chars, i + 1, &['"']).map_or(chars.len(), |end| end + 1)
+        }
+        else if c.is_alphanumeric() || c == '_'
+        {
+            // Palavra inteira, para que o `q` final de um nome não abra um literal q'...'.
+            (i..chars.len())
+                .find(|&j| !(chars[j].is_alphanumeric() || matches!(chars[j], '_' | '$' | '#')))
+                .unwrap_or(chars.len())
+        }
+        else
+        {
+            i + 1
+        };
+
+        if space && !normalized.is_empty() && !normalized.ends_with('\n')
+        {
+            normalized.push(' ');
+        }
+        space = false;
+        normalized.extend(&chars[i..end]);
+        i = end;
+    }
+    normalized.trim_end_matches(';').trim_end().to_string()
+}
+
+/// `SqlEngine` que guarda em cache os resultados de `query_df` de outro engine.
+///
+/// `execute` e `query_df_stream` passam direto, sem cache; após escritas, use
+/// `cache().invalidate_prefix(...)` nas consultas afetadas.
+pub struct CachedEngine
+{
+    inner: Arc<dyn SqlEngine>,
+    cache: QueryCache,
+}
+
+impl CachedEngine
+{
+    pub fn new(inner: Arc<dyn SqlEngine>, config: CacheConfig) -> Self
+    {
+        Self {
+            inner,
+            cache: QueryCache::new(config),
+        }
+    }
+
+    pub fn cache(&self) -> &QueryCache
+    {
+        &self.cache
+    }
+}
+
+impl SqlEngine for CachedEngine
+{
+    fn backend_name(&self) -> &'static str
+    {
+        self.inner.backend_name()
+    }
+
+    /// Usa `options.cache_ttl` (ou o TTL padrão); `Some(Duration::ZERO)` ignora o cache.
+    fn query_df(
+        &self,
+        sql: &str,
+        params: &[SqlValue],
+        options: &QueryOptions,
+        cancel: Option<&CancelToken>,
+    ) -> EngineResult<DataFrame>
+    {
+        let ttl = options.cache_ttl.unwrap_or(self.cache.config.default_ttl);
+        if ttl.is_zero()
+        {
+            return self.inner.query_df(sql, params, options, cancel);
+        }
+
+        let key = cache_key(sql, params, options);
+        if let Some(df) = self.cache.get(&key)
+        {
+            debug!("Cache hit: {}", key);
+            return Ok(df);
+        }
+
+        let df = self.inner.query_df(sql, params, options, cancel)?;
+        self.cache.insert(key, df.clone(), ttl);
+        Ok(df)
+    }
+
+    fn execute(&self, sql: &str, params: &[SqlValue]) -> EngineResult<u64>
+    {
+        self.inner.execute(sql, params)
+    }
+
+    fn query_df_stream<'a>(
+        &'a self,
+        sql: &str,
+        params: &[SqlValue],
+        chunk_rows: usize,
+        options: &QueryOptions,
+    ) -> EngineResult<DataFrameChunks<'a>>
+    {
+        self.inner.query_df_stream(sql, params, chunk_rows, options)
+    }
+
+    fn ping(&self) -> EngineResult<Duration>
+    {
+        self.inner.ping()
+    }
+
+    fn pool_stats(&self) -> EngineResult<Option<PoolStats>>
+    {
+        self.inner.pool_stats()
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use polars::prelude::df;
+
+    use super::*;
+    use crate::oracle::lob::LobMode;
+
+    fn frame(rows: i64) -> DataFrame
+    {
+        df!("ID" => (0..rows).collect::<Vec<i64>>()).unwrap()
+    }
+
+    #[test]
+    fn normalize_sql_collapses_whitespace_outside_literals()
+    {
+        assert_eq!(normalize_sql("  SELECT *\n\t FROM   dual ;\n"), "SELECT * FROM dual");
+        assert_eq!(
+            normalize_sql("SELECT 'a  b', \"Col  X\", q'[c  d]' FROM dual"),
+            "SELECT 'a  b', \"Col  X\", q'[c  d]' FROM dual"
+        );
+        assert_eq!(
+            normalize_sql("SELECT 'it''s  ok'   FROM dual"),
+            "SELECT 'it''s  ok' FROM dual"
+        );
+        assert_eq!(
+            normalize_sql("SELECT /*+  FULL(t) */  1   FROM t"),
+            "SELECT /*+  FULL(t) */ 1 FROM t"
+        );
+        assert_eq!(
+            normalize_sql("SELECT 1 -- um  comentário\n    FROM dual"),
+            "SELECT 1 -- um  comentário\nFROM dual"
+        );
+        assert_eq!(normalize_sql("SELECT seq'x'  FROM t"), "SELECT seq'x' FROM t");
+        assert_eq!(normalize_sql("SELECT  'aberto   x"), "SELECT 'aberto   x");
+    }
+
+    #[test]
+    fn cache_key_keeps_literals_apart()
+    {
+        let options = QueryOptions::default();
+        assert_ne!(
+            cache_key("SELECT * FROM t WHERE x = 'a  b'", &[], &options),
+            cache_key("SELECT * FROM t WHERE x = 'a b'", &[], &options)
+        );
+        assert_eq!(
+            cache_key("SELECT *\n  FROM t WHERE x = 'a  b';", &[], &options),
+            cache_key("SELECT * FROM t WHERE x = 'a  b'", &[], &options)
+        );
+    }
+
+    #[test]
+    fn cache_key_depends_on_binds_and_options()
+    {
+        let options = QueryOptions::default();
+        let sql = "SELECT * FROM t WHERE id = :1";
+        let key = cache_key(sql, &[SqlValue::Int(1)], &options);
+        assert!(key.starts_with(&normalize_sql(sql)));
+        assert_ne!(key, cache_key(sql, &[SqlValue::Int(2)], &options));
+
+        let truncated = QueryOptions::default().lob_column("DOC", LobMode::Truncate(10));
+        assert_ne!(key, cache_key(sql, &[SqlValue::Int(1)], &truncated));
+    }
+
+    #[test]
+    fn evicts_least_recently_used_when_over_budget()
+    {
+        let size = frame(100).estimated_size();
+        let cache = QueryCache::new(CacheConfig {
+            default_ttl: Duration::from_secs(60),
+            max_bytes: size * 2,
+        });
+        let ttl = Duration::from_secs(60);
+        cache.insert("a", frame(100), ttl);
+        cache.insert("b", frame(100), ttl);
+        assert!(cache.get("a").is_some());
+
+        // "b" é o menos usado desde o get de "a"
+        cache.insert("c", frame(100), ttl);
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("c").is_some());
+
+        let stats = cache.stats();
+        assert_eq!(stats.evictions, 1);
+        assert_eq!(stats.entries, 2);
+        assert_eq!(stats.bytes, size * 2);
+        assert_eq!((stats.hits, stats.misses), (3, 1));
+
+        // Maior que o orçamento inteiro: não entra e não descarta ninguém
+        cache.insert("d", frame(1000), ttl);
+        assert!(cache.get("d").is_none());
+        assert_eq!(cache.stats().entries, 2);
+    }
+
+    #[test]
+    fn expired_entries_and_prefix_invalidation()
+    {
+        let cache = QueryCache::new(CacheConfig::default());
+        cache.insert("x", frame(1), Duration::from_millis(1));
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(cache.get("x").is_none());
+        assert_eq!(cache.stats().entries, 0);
+
+        let options = QueryOptions::default();
+        let sql = "SELECT * FROM t WHERE id = :1";
+        for id in 1..=3
+        {
+            cache.insert(
+                cache_key(sql, &[SqlValue::Int(id)], &options),
+                frame(1),
+                Duration::from_secs(60),
+            );
+        }
+        cache.insert(cache_key("SELECT * FROM u", &[], &options), frame(1), Duration::from_secs(60));
+        assert_eq!(cache.invalidate_prefix("SELECT *\n  FROM t"), 3);
+        assert_eq!(cache.stats().entries, 1);
+    }
+}
@@ -0,0 +1,141 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use log::info;
+use log::warn;
+use oracle::Connection;
+
+use super::error::EngineError;
+use super::error::EngineResult;
+
+/// Sinal de cancelamento compartilhado entre quem dispara a consulta e a thread que faz o fetch.
+///
+/// O fetch verifica o sinal a cada linha e, no Oracle, a instrução em execução no servidor
+/// é interrompida (`break_execution`) assim que ele é acionado.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    pub fn cancel(&self)
+    {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool
+    {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Aciona o `CancelToken` ao ser descartado, por exemplo quando o future do handler HTTP
+/// é abandonado porque o cliente desconectou.
+pub(crate) struct CancelOnDrop(pub(crate) CancelToken);
+
+impl Drop for CancelOnDrop
+{
+    fn drop(&mut self)
+    {
+        self.0.cancel();
+    }
+}
+
+/// Intervalo entre verificações do cancelamento e do prazo pela thread de vigia.
+const WATCH_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Executa `work` na conexão aplicando o timeout e o cancelamento no servidor.
+///
+/// - `timeout` vira o call timeout da conexão (limite de cada round-trip) e também o prazo
+///   total da chamada, já que uma consulta grande faz vários round-trips
+/// - ao estourar o prazo ou ao acionar `cancel`, uma thread de vigia chama `break_execution`,
+///   interrompendo a instrução no servidor, e aciona o token repassado a `work`
+///
+/// Estouro do prazo resulta em `EngineError::Timeout`; cancelamento, em `EngineError::Cancelled`.
+///
+/// Call timeout e `break_execution` valem para a sessão inteira: `conn` precisa estar em uso
+/// exclusivo desta chamada (conexão do pool ou a conexão única com seu `SessionGuard`).
+pub(crate) fn run_guarded<T>(
+    conn: &Connection,
+    cancel: Option<&CancelToken>,
+    timeout: Option<Duration>,
+    work: impl FnOnce(&CancelToken) -> EngineResult<T>,
+) -> EngineResult<T>
+{
+    let token = CancelToken::new();
+    if cancel.is_none() && timeout.is_none()
+    {
+        return work(&token);
+    }
+
+    conn.set_call_timeout(timeout)?;
+    let deadline = timeout.map(|t| Instant::now() + t);
+    let timed_out = AtomicBool::new(false);
+    let (done_tx, done_rx) = mpsc::channel::<()>();
+
+    let result = thread::scope(|scope| {
+        let (token, timed_out) = (&token, &timed_out);
+        scope.spawn(move || {
+            loop
+            {
+                let expired = deadline.is_some_and(|d| Instant::now() >= d);
+                if expired || cancel.is_some_and(|c| c.is_cancelled())
+                {
+                    timed_out.store(expired, Ordering::Relaxed);
+                    token.cancel();
+                    if let Err(e) = conn.break_execution()
+                    {
+                        warn!("Falha ao interromper a instrução no servidor: {}", e);
+                    }
+                    return;
+                }
+                // Encerra quando `work` termina (o remetente é descartado)
+                if done_rx.recv_timeout(WATCH_INTERVAL) != Err(RecvTimeoutError::Timeout)
+                {
+                    return;
+                }
+            }
+        });
+
+        let result = work(token);
+        drop(done_tx);
+        result
+    });
+
+    // A conexão pode voltar ao pool: remove o call timeout desta chamada
+    clear_call_timeout(conn);
+
+    match result
+    {
+        Err(EngineError::Cancelled { sql_id, source } | EngineError::Timeout { sql_id, source, .. })
+            if timed_out.load(Ordering::Relaxed) =>
+        {
+            info!("Prazo de {:?} esgotado; instrução interrompida.", timeout.unwrap_or_default());
+            Err(EngineError::Timeout {
+                code: None,
+                sql_id,
+                source,
+            })
+        },
+        other => other,
+    }
+}
+
+/// Remove o call timeout da sessão antes que ela seja reutilizada; a falha só é registrada.
+pub(crate) fn clear_call_timeout(conn: &Connection)
+{
+    if let Err(e) = conn.set_call_timeout(None)
+    {
+        warn!("Falha ao remover o call timeout da conexão: {}", e);
+    }
+}
@@ -0,0 +1,141 @@
+use oracle::sql_type::ToSql;
+use polars::prelude::*;
+
+use super::connection::EngineOracle;
+use super::error::EngineResult;
+use super::options::QueryOptions;
+use super::sql::quote_table_name;
+use super::sql::split_table_name;
+use super::sql::stored_name;
+use super::stream::open_result_set;
+use super::stream::result_schema;
+
+/// Dono usado quando a tabela vem sem schema: o schema corrente da sessão.
+const OWNER: &str = "NVL(:1, SYS_CONTEXT('USERENV', 'CURRENT_SCHEMA'))";
+
+const SCHEMAS_SQL: &str = "
+    SELECT USERNAME AS SCHEMA_NAME, CREATED
+    FROM ALL_USERS
+    ORDER BY USERNAME
+";
+
+const TABLES_SQL: &str = "
+    SELECT t.OWNER, t.TABLE_NAME, CAST(t.NUM_ROWS AS NUMBER(18)) AS NUM_ROWS, t.LAST_ANALYZED, c.COMMENTS
+    FROM ALL_TABLES t
+    LEFT JOIN ALL_TAB_COMMENTS c
+        ON c.OWNER = t.OWNER AND c.TABLE_NAME = t.TABLE_NAME
+    WHERE t.OWNER = {owner}
+    ORDER BY t.TABLE_NAME
+";
+
+const COLUMNS_SQL: &str = "
+    SELECT
+        CAST(c.COLUMN_ID AS NUMBER(10)) AS COLUMN_ID,
+        c.COLUMN_NAME,
+        c.DATA_TYPE,
+        CAST(c.DATA_LENGTH AS NUMBER(10)) AS DATA_LENGTH,
+        CAST(c.CHAR_LENGTH AS NUMBER(10)) AS CHAR_LENGTH,
+        CAST(c.DATA_PRECISION AS NUMBER(10)) AS DATA_PRECISION,
+        CAST(c.DATA_SCALE AS NUMBER(10)) AS DATA_SCALE,
+        c.NULLABLE,
+        m.COMMENTS
+    FROM ALL_TAB_COLUMNS c
+    LEFT JOIN ALL_COL_COMMENTS m
+        ON m.OWNER = c.OWNER AND m.TABLE_NAME = c.TABLE_NAME AND m.COLUMN_NAME = c.COLUMN_NAME
+    WHERE c.OWNER = {owner} AND c.TABLE_NAME = :2
+    ORDER BY c.COLUMN_ID
+";
+
+const PRIMARY_KEY_SQL: &str = "
+    SELECT c.CONSTRAINT_NAME, cc.COLUMN_NAME, CAST(cc.POSITION AS NUMBER(10)) AS POSITION
+    FROM ALL_CONSTRAINTS c
+    JOIN ALL_CONS_COLUMNS cc
+        ON cc.OWNER = c.OWNER AND cc.CONSTRAINT_NAME = c.CONSTRAINT_NAME
+    WHERE c.CONSTRAINT_TYPE = 'P' AND c.OWNER = {owner} AND c.TABLE_NAME = :2
+    ORDER BY cc.POSITION
+";
+
+const INDEXES_SQL: &str = "
+    SELECT
+        i.INDEX_NAME,
+        i.INDEX_TYPE,
+        i.UNIQUENESS,
+        ic.COLUMN_NAME,
+        CAST(ic.COLUMN_POSITION AS NUMBER(10)) AS COLUMN_POSITION,
+        ic.DESCEND
+    FROM ALL_INDEXES i
+    JOIN ALL_IND_COLUMNS ic
+        ON ic.INDEX_OWNER = i.OWNER AND ic.INDEX_NAME = i.INDEX_NAME
+    WHERE i.TABLE_OWNER = {owner} AND i.TABLE_NAME = :2
+    ORDER BY i.INDEX_NAME, ic.COLUMN_POSITION
+";
+
+/// Consultas ao dicionário de dados (`ALL_*`), devolvidas como DataFrames.
+///
+/// As tabelas aceitam `TABELA` ou `SCHEMA.TABELA`; sem schema, vale o schema corrente da sessão.
+/// Só aparecem objetos que o usuário conectado tem permissão de ver. As colunas numéricas do
+/// dicionário são NUMBER sem precisão; nas consultas elas passam por CAST para chegar como `Int64`.
+impl EngineOracle
+{
+    /// Schemas visíveis (`ALL_USERS`).
+    pub fn list_schemas(&self) -> EngineResult<DataFrame>
+    {
+        self.query_to_polars_df(SCHEMAS_SQL, &[])
+    }
+
+    /// Tabelas do schema, com número de linhas da última coleta de estatísticas e comentário.
+    ///
+    /// `None` lista as tabelas do schema corrente.
+    pub fn list_tables(&self, owner: Option<&str>) -> EngineResult<DataFrame>
+    {
+        let owner = owner.map(stored_name).transpose()?;
+        self.query_to_polars_df(&TABLES_SQL.replace("{owner}", OWNER), &[&owner])
+    }
+
+    /// Colunas da tabela com tipo Oracle, tamanho, precisão, escala, nulabilidade e comentário.
+    pub fn list_columns(&self, table: &str) -> EngineResult<DataFrame>
+    {
+        let options = QueryOptions::default().boolean_column("NULLABLE");
+        self.catalog_query(COLUMNS_SQL, table, &options)
+    }
+
+    /// Colunas da chave primária, na ordem da constraint (DataFrame vazio se não houver).
+    pub fn primary_key(&self, table: &str) -> EngineResult<DataFrame>
+    {
+        self.catalog_query(PRIMARY_KEY_SQL, table, &QueryOptions::default())
+    }
+
+    /// Índices da tabela, uma linha por coluna indexada.
+    pub fn list_indexes(&self, table: &str) -> EngineResult<DataFrame>
+    {
+        self.catalog_query(INDEXES_SQL, table, &QueryOptions::default())
+    }
+
+    /// `Schema` do Polars que `query_to_polars_df_with` produziria para `SELECT * FROM table`.
+    ///
+    /// Usa os metadados da própria consulta (sem ler linhas), então segue exatamente o mapeamento
+    /// de tipos e as opções informadas. No modo somente leitura, a tabela passa pela mesma
+    /// conferência do guard que as consultas.
+    pub fn table_polars_schema(&self, table: &str, options: &QueryOptions) -> EngineResult<Schema>
+    {
+        let sql = format!("SELECT * FROM {} WHERE 1 = 0", quote_table_name(table)?);
+        self.check_read(&sql).map_err(|e| e.with_sql(&sql))?;
+        let conn = self.connection_with(&options.session)?;
+        let rows = open_result_set(&conn, &sql, &[], options).map_err(|e| e.with_sql(&sql))?;
+        let schema = result_schema(&rows, options);
+
+        Ok(schema
+            .names
+            .iter()
+            .zip(schema.output_dtypes())
+            .map(|(name, dtype)| Field::new(name.as_str().into(), dtype))
+            .collect())
+    }
+
+    fn catalog_query(&self, sql: &str, table: &str, options: &QueryOptions) -> EngineResult<DataFrame>
+    {
+        let (owner, name) = split_table_name(table)?;
+        let params: [&dyn ToSql; 2] = [&owner, &name];
+        self.query_to_polars_df_with(&sql.replace("{owner}", OWNER), &params, options)
+    }
+}
@@ -0,0 +1,942 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::guard::SqlGuard;
+use super::plan::CostAction;
+use super::plan::CostLimit;
+use super::pool::whole_secs;
+use super::pool::PoolConfig;
+use super::retry::RetryPolicy;
+use super::session::SessionConfig;
+use super::session::SessionTag;
+use super::sql::quote_identifier;
+use super::sql::stored_name;
+
+/// Porta padrão do listener Oracle, usada quando apenas host e service name são informados.
+pub const DEFAULT_PORT: u16 = 1521;
+
+/// Erros de leitura ou validação da configuração, sempre indicando o campo problemático.
+#[derive(Debug, Error)]
+pub enum ConfigError
+{
+    #[error("campo obrigatório ausente: {field}")]
+    Missing
+    {
+        field: &'static str
+    },
+
+    #[error("valor inválido em {field}: {reason}")]
+    Invalid
+    {
+        field: &'static str, reason: String
+    },
+
+    #[error("falha ao ler {}: {source}", path.display())]
+    Io
+    {
+        path: PathBuf, source: std::io::Error
+    },
+
+    #[error("TOML inválido: {0}")]
+    Toml(#[from] toml::de::Error),
+}
+
+/// Configuração validada do `EngineOracle`.
+///
+/// Pode ser montada a partir do ambiente (`from_env`), de um arquivo TOML (`from_toml_file`)
+/// ou em código (`OracleConfig::builder()`).
+#[derive(Clone)]
+pub struct OracleConfig
+{
+    pub username: String,
+    pub password: String,
+    /// String de conexão passada ao driver (`host:port/service` ou um alias do tnsnames).
+    pub connect_string: String,
+    /// Diretório do Oracle Instant Client, usado na inicialização do driver.
+    pub client_path: Option<PathBuf>,
+    /// Quando presente, o engine usa um pool; caso contrário, uma conexão única.
+    pub pool: Option<PoolConfig>,
+    /// Timeout padrão das consultas; `QueryOptions::timeout` tem prioridade.
+    pub call_timeout: Option<Duration>,
+    /// Novas tentativas de leituras que falham por perda de conexão.
+    pub retry: RetryPolicy,
+    /// Identificação padrão das sessões e ajustes (NLS, schema) aplicados a cada conexão nova.
+    pub session: SessionConfig,
+    /// Quando presente, o engine só executa leituras aprovadas pelo `SqlGuard`.
+    pub guard: Option<SqlGuard>,
+    /// Limite de custo do plano conferido antes de cada consulta; `QueryOptions::cost_limit` tem prioridade.
+    pub cost_limit: Option<CostLimit>,
+}
+
+impl fmt::Debug for OracleConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        f.debug_struct("OracleConfig")
+            .field("username", &self.username)
+            .field("password", &"***")
+            .field("connect_string", &self.connect_string)
+            .field("client_path", &self.client_path)
+            .field("pool", &self.pool)
+            .field("call_timeout", &self.call_timeout)
+            .field("retry", &self.retry)
+            .field("session", &self.session)
+            .field("guard", &self.guard)
+            .field("cost_limit", &self.cost_limit)
+            .finish()
+    }
+}
+
+impl OracleConfig
+{
+    pub fn builder() -> OracleConfigBuilder
+    {
+        OracleConfigBuilder::default()
+    }
+
+    /// Lê a configuração das variáveis de ambiente (veja `.env.example`).
+    ///
+    /// `ORACLE_DSN` tem prioridade sobre `ORACLE_HOST`/`ORACLE_PORT`/`ORACLE_SERVICE_NAME`.
+    /// O pool é ativado quando `ORACLE_POOL_MAX` está definida (`ORACLE_POOL_PING_INTERVAL_SECS=0`
+    /// desativa o ping das conexões ociosas), o timeout padrão vem de
+    /// `ORACLE_CALL_TIMEOUT_SECS`, a política de novas tentativas de `ORACLE_RETRY_*` e os
+    /// ajustes de sessão de `ORACLE_MODULE`, `ORACLE_ACTION`, `ORACLE_CLIENT_IDENTIFIER`,
+    /// `ORACLE_NLS_*` e `ORACLE_CURRENT_SCHEMA`. O modo somente leitura é ativado por
+    /// `ORACLE_GUARD=true`, com os schemas de `ORACLE_GUARD_SCHEMAS` (separados por vírgula), e o
+    /// limite de custo por `ORACLE_MAX_COST` e `ORACLE_COST_ACTION` (`warn` ou `refuse`).
+    pub fn from_env() -> Result<Self, ConfigError>
+    {
+        let pool = match env_parse::<u32>("ORACLE_POOL_MAX")?
+        {
+            Some(max_connections) => Some(PoolSettings {
+                min_connections: env_parse("ORACLE_POOL_MIN")?,
+                max_connections: Some(max_connections),
+                connection_increment: env_parse("ORACLE_POOL_INCREMENT")?,
+                idle_timeout_secs: env_parse("ORACLE_POOL_IDLE_TIMEOUT_SECS")?,
+                ping_interval_secs: env_parse("ORACLE_POOL_PING_INTERVAL_SECS")?,
+                checkout_timeout_ms: env_secs_as_ms("ORACLE_POOL_CHECKOUT_TIMEOUT_SECS")?,
+            }),
+            None => None,
+        };
+
+        let retry = RetrySettings {
+            max_attempts: env_parse("ORACLE_RETRY_MAX_ATTEMPTS")?,
+            initial_backoff_ms: env_parse("ORACLE_RETRY_INITIAL_BACKOFF_MS")?,
+            max_backoff_ms: env_parse("ORACLE_RETRY_MAX_BACKOFF_MS")?,
+            multiplier: env_parse("ORACLE_RETRY_MULTIPLIER")?,
+            jitter: env_parse("ORACLE_RETRY_JITTER")?,
+        };
+
+        let session = SessionSettings {
+            module: env_string("ORACLE_MODULE"),
+            action: env_string("ORACLE_ACTION"),
+            client_identifier: env_string("ORACLE_CLIENT_IDENTIFIER"),
+            nls_date_format: env_string("ORACLE_NLS_DATE_FORMAT"),
+            nls_timestamp_format: env_string("ORACLE_NLS_TIMESTAMP_FORMAT"),
+            nls_numeric_characters: env_string("ORACLE_NLS_NUMERIC_CHARACTERS"),
+            current_schema: env_string("ORACLE_CURRENT_SCHEMA"),
+            statements: Vec::new(),
+        };
+
+        let guard = match env_parse::<bool>("ORACLE_GUARD")?
+        {
+            Some(true) => Some(GuardSettings {
+                allowed_schemas: env_string("ORACLE_GUARD_SCHEMAS")
+                    .map(|schemas| schemas.split(',').map(|s| s.trim().to_string()).collect())
+                    .unwrap_or_default(),
+                read_only_session: env_parse("ORACLE_GUARD_READ_ONLY_SESSION")?,
+            }),
+            _ => None,
+        };
+
+        let cost_limit = match env_parse::<u64>("ORACLE_MAX_COST")?
+        {
+            Some(max_cost) => Some(CostLimitSettings {
+                max_cost: Some(max_cost),
+                action: env_parse("ORACLE_COST_ACTION")?,
+            }),
+            None => None,
+        };
+
+        OracleConfigBuilder {
+            username: env_string("ORACLE_USERNAME"),
+            password: env_string("ORACLE_PASSWORD"),
+            dsn: env_string("ORACLE_DSN"),
+            host: env_string("ORACLE_HOST"),
+            port: env_parse("ORACLE_PORT")?,
+            service_name: env_string("ORACLE_SERVICE_NAME"),
+            client_path: env_string("CLIENT_PATH").map(PathBuf::from),
+            pool,
+            call_timeout_ms: env_secs_as_ms("ORACLE_CALL_TIMEOUT_SECS")?,
+            retry: Some(retry),
+            session: Some(session),
+            guard,
+            cost_limit,
+        }
+        .build()
+    }
+
+    /// Lê a configuração de um arquivo TOML com as mesmas chaves de `OracleConfigBuilder`.
+    ///
+    /// ```toml
+    /// username = "app"
+    /// password = "secret"
+    /// dsn = "dbhost:1521/ORCL"
+    ///
+    /// [pool]
+    /// max_connections = 10
+    ///
+    /// [session]
+    /// module = "chassi-api"
+    /// nls_date_format = "YYYY-MM-DD HH24:MI:SS"
+    ///
+    /// [guard]
+    /// allowed_schemas = ["SYSADM"]
+    ///
+    /// [cost_limit]
+    /// max_cost = 50000
+    /// action = "refuse"
+    /// ```
+    pub fn from_toml_file(path: impl AsRef<Path>) -> Result<Self, ConfigError>
+    {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::from_toml_str(&text)
+    }
+
+    pub fn from_toml_str(text: &str) -> Result<Self, ConfigError>
+    {
+        toml::from_str::<OracleConfigBuilder>(text)?.build()
+    }
+}
+
+/// Campos ainda não validados da configuração; `build()` confere cada um.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OracleConfigBuilder
+{
+    username: Option<String>,
+    password: Option<String>,
+    dsn: Option<String>,
+    host: Option<String>,
+    port: Option<u16>,
+    service_name: Option<String>,
+    client_path: Option<PathBuf>,
+    pool: Option<PoolSettings>,
+    call_timeout_ms: Option<u64>,
+    retry: Option<RetrySettings>,
+    session: Option<SessionSettings>,
+    guard: Option<GuardSettings>,
+    cost_limit: Option<CostLimitSettings>,
+}
+
+/// Seção `[pool]` da configuração. O driver só aceita segundos inteiros para a ociosidade e o
+/// ping; a espera por conexão livre é em milissegundos.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PoolSettings
+{
+    pub min_connections: Option<u32>,
+    pub max_connections: Option<u32>,
+    pub connection_increment: Option<u32>,
+    /// `0` nunca encerra as conexões ociosas.
+    pub idle_timeout_secs: Option<u64>,
+    /// Ausente usa o padrão do `PoolConfig`; `0` desativa o ping.
+    pub ping_interval_secs: Option<u64>,
+    pub checkout_timeout_ms: Option<u64>,
+}
+
+/// Seção `[retry]` da configuração, com tempos em milissegundos.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RetrySettings
+{
+    pub max_attempts: Option<u32>,
+    pub initial_backoff_ms: Option<u64>,
+    pub max_backoff_ms: Option<u64>,
+    /// Fator aplicado à espera a cada nova tentativa (1.0 mantém a espera constante).
+    pub multiplier: Option<f64>,
+    pub jitter: Option<f64>,
+}
+
+/// Seção `[session]` da configuração.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SessionSettings
+{
+    pub module: Option<String>,
+    pub action: Option<String>,
+    pub client_identifier: Option<String>,
+    pub nls_date_format: Option<String>,
+    pub nls_timestamp_format: Option<String>,
+    pub nls_numeric_characters: Option<String>,
+    pub current_schema: Option<String>,
+    /// Instruções extras executadas em cada conexão nova (ex.: `ALTER SESSION SET TIME_ZONE = 'UTC'`).
+    #[serde(default)]
+    pub statements: Vec<String>,
+}
+
+/// Seção `[guard]` da configuração; presente, ativa o modo somente leitura.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GuardSettings
+{
+    #[serde(default)]
+    pub allowed_schemas: Vec<String>,
+    pub read_only_session: Option<bool>,
+}
+
+/// Seção `[cost_limit]` da configuração.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CostLimitSettings
+{
+    pub max_cost: Option<u64>,
+    pub action: Option<CostAction>,
+}
+
+impl OracleConfigBuilder
+{
+    pub fn username(mut self, username: impl Into<String>) -> Self
+    {
+        self.username = Some(username.into());
+        self
+    }
+
+    pub fn password(mut self, password: impl Into<String>) -> Self
+    {
+        self.password = Some(password.into());
+        self
+    }
+
+    pub fn dsn(mut self, dsn: impl Into<String>) -> Self
+    {
+        self.dsn = Some(dsn.into());
+        self
+    }
+
+    pub fn host(mut self, host: impl Into<String>) -> Self
+    {
+        self.host = Some(host.into());
+        self
+    }
+
+    pub fn port(mut self, port: u16) -> Self
+    {
+        self.port = Some(port);
+        self
+    }
+
+    pub fn service_name(mut self, service_name: impl Into<String>) -> Self
+    {
+        self.service_name = Some(service_name.into());
+        self
+    }
+
+    pub fn client_path(mut self, client_path: impl Into<PathBuf>) -> Self
+    {
+        self.client_path = Some(client_path.into());
+        self
+    }
+
+    pub fn pool(mut self, pool: PoolConfig) -> Self
+    {
+        self.pool = Some(PoolSettings {
+            min_connections: Some(pool.min_connections),
+            max_connections: Some(pool.max_connections),
+            connection_increment: Some(pool.connection_increment),
+            idle_timeout_secs: Some(whole_secs(pool.idle_timeout).as_secs()),
+            // 0 significa ping desativado; `Some(Duration::ZERO)` (validar toda retirada) vira 1 s
+            ping_interval_secs: Some(pool.ping_interval.map_or(0, |d| whole_secs(d).as_secs().max(1))),
+            checkout_timeout_ms: Some(millis(pool.checkout_timeout)),
+        });
+        self
+    }
+
+    /// Timeout padrão das consultas, com precisão de milissegundos (o Oracle não aceita menos).
+    pub fn call_timeout(mut self, timeout: Duration) -> Self
+    {
+        // Arredonda para cima o que ficaria abaixo de 1 ms, para não virar 0 (inválido)
+        self.call_timeout_ms = Some(if timeout.is_zero() { 0 } else { millis(timeout).max(1) });
+        self
+    }
+
+    pub fn retry(mut self, retry: RetryPolicy) -> Self
+    {
+        self.retry = Some(RetrySettings {
+            max_attempts: Some(retry.max_attempts),
+            initial_backoff_ms: Some(millis(retry.initial_backoff)),
+            max_backoff_ms: Some(millis(retry.max_backoff)),
+            multiplier: Some(retry.multiplier),
+            jitter: Some(retry.jitter),
+        });
+        self
+    }
+
+    pub fn session(mut self, session: SessionConfig) -> Self
+    {
+        self.session = Some(SessionSettings {
+            module: session.tag.module,
+            action: session.tag.action,
+            client_identifier: session.tag.client_identifier,
+            nls_date_format: session.nls_date_format,
+            nls_timestamp_format: session.nls_timestamp_format,
+            nls_numeric_characters: session.nls_numeric_characters,
+            current_schema: session.current_schema,
+            statements: session.statements,
+        });
+        self
+    }
+
+    pub fn guard(mut self, guard: SqlGuard) -> Self
+    {
+        self.guard = Some(GuardSettings {
+            allowed_schemas: guard.allowed_schemas,
+            read_only_session: Some(guard.read_only_session),
+        });
+        self
+    }
+
+    pub fn cost_limit(mut self, cost_limit: CostLimit) -> Self
+    {
+        self.cost_limit = Some(CostLimitSettings {
+            max_cost: Some(cost_limit.max_cost),
+            action: Some(cost_limit.action),
+        });
+        self
+    }
+
+    /// Valida os campos e produz a configuração final.
+    pub fn build(self) -> Result<OracleConfig, ConfigError>
+    {
+        let username = required("username", self.username)?;
+        let password = required("password", self.password)?;
+
+        let connect_string = match self.dsn
+        {
+            Some(dsn) =>
+            {
+                let dsn = non_empty("dsn", dsn)?;
+                if dsn.chars().any(char::is_whitespace)
+                {
+                    return Err(ConfigError::Invalid {
+                        field: "dsn",
+                        reason: "não pode conter espaços".to_string(),
+                    });
+                }
+                dsn
+            },
+            None =>
+            {
+                let host = required("host", self.host)?;
+                let service_name = required("service_name", self.service_name)?;
+                let port = self.port.unwrap_or(DEFAULT_PORT);
+                if port == 0
+                {
+                    return Err(ConfigError::Invalid {
+                        field: "port",
+                        reason: "deve ser maior que zero".to_string(),
+                    });
+                }
+                // Formato esperado pelo rust-oracle: //host:port/service_name
+                format!("//{}:{}/{}", host, port, service_name)
+            },
+        };
+
+        if let Some(path) = &self.client_path
+        {
+            if !path.is_dir()
+            {
+                return Err(ConfigError::Invalid {
+                    field: "client_path",
+                    reason: format!("diretório não encontrado: {}", path.display()),
+                });
+            }
+        }
+
+        let pool = self.pool.map(PoolSettings::build).transpose()?;
+
+        let call_timeout = match self.call_timeout_ms
+        {
+            Some(0) =>
+            {
+                return Err(ConfigError::Invalid {
+                    field: "call_timeout_ms",
+                    reason: "deve ser maior que zero".to_string(),
+                });
+            },
+            ms => ms.map(Duration::from_millis),
+        };
+
+        let retry = self.retry.unwrap_or_default().build()?;
+        let session = self.session.unwrap_or_default().build()?;
+        let guard = self.guard.map(GuardSettings::build).transpose()?;
+        let cost_limit = self.cost_limit.map(CostLimitSettings::build).transpose()?;
+        if guard.as_ref().is_some_and(|g| g.read_only_session) && pool.is_none()
+        {
+            // SET TRANSACTION não convive com leituras concorrentes na mesma sessão
+            return Err(ConfigError::Invalid {
+                field: "guard.read_only_session",
+                reason: "exige pool de conexões".to_string(),
+            });
+        }
+
+        Ok(OracleConfig {
+            username,
+            password,
+            connect_string,
+            client_path: self.client_path,
+            pool,
+            call_timeout,
+            retry,
+            session,
+            guard,
+            cost_limit,
+        })
+    }
+}
+
+impl PoolSettings
+{
+    fn build(self) -> Result<PoolConfig, ConfigError>
+    {
+        let defaults = PoolConfig::default();
+        let pool = PoolConfig {
+            min_connections: self.min_connections.unwrap_or(defaults.min_connections),
+            max_connections: self.max_connections.unwrap_or(defaults.max_connections),
+            connection_increment: self.connection_increment.unwrap_or(defaults.connection_increment),
+            idle_timeout: self
+                .idle_timeout_secs
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.idle_timeout),
+            ping_interval: match self.ping_interval_secs
+            {
+                None => defaults.ping_interval,
+                Some(0) => None,
+                Some(secs) => Some(Duration::from_secs(secs)),
+            },
+            checkout_timeout: self
+                .checkout_timeout_ms
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.checkout_timeout),
+        };
+
+        if pool.max_connections == 0
+        {
+            return Err(ConfigError::Invalid {
+                field: "pool.max_connections",
+                reason: "deve ser maior que zero".to_string(),
+            });
+        }
+        if pool.min_connections > pool.max_connections
+        {
+            return Err(ConfigError::Invalid {
+                field: "pool.min_connections",
+                reason: format!(
+                    "{} é maior que max_connections ({})",
+                    pool.min_connections, pool.max_connections
+                ),
+            });
+        }
+
+        Ok(pool)
+    }
+}
+
+impl RetrySettings
+{
+    fn build(self) -> Result<RetryPolicy, ConfigError>
+    {
+        let defaults = RetryPolicy::default();
+        let retry = RetryPolicy {
+            max_attempts: self.max_attempts.unwrap_or(defaults.max_attempts),
+            initial_backoff: self
+                .initial_backoff_ms
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.initial_backoff),
+            max_backoff: self.max_backoff_ms.map(Duration::from_millis).unwrap_or(defaults.max_backoff),
+            multiplier: self.multiplier.unwrap_or(defaults.multiplier),
+            jitter: self.jitter.unwrap_or(defaults.jitter),
+        };
+
+        if retry.max_attempts == 0
+        {
+            return Err(ConfigError::Invalid {
+                field: "retry.max_attempts",
+                reason: "deve ser maior que zero (1 desativa as novas tentativas)".to_string(),
+            });
+        }
+        if retry.initial_backoff > retry.max_backoff
+        {
+            return Err(ConfigError::Invalid {
+                field: "retry.initial_backoff_ms",
+                reason: format!(
+                    "{:?} é maior que max_backoff_ms ({:?})",
+                    retry.initial_backoff, retry.max_backoff
+                ),
+            });
+        }
+        if !(retry.multiplier.is_finite() && retry.multiplier >= 1.0)
+        {
+            return Err(ConfigError::Invalid {
+                field: "retry.multiplier",
+                reason: "deve ser um número a partir de 1.0".to_string(),
+            });
+        }
+        if !(0.0..=1.0).contains(&retry.jitter)
+        {
+            return Err(ConfigError::Invalid {
+                field: "retry.jitter",
+                reason: "deve estar entre 0.0 e 1.0".to_string(),
+            });
+        }
+
+        Ok(retry)
+    }
+}
+
+impl SessionSettings
+{
+    fn build(self) -> Result<SessionConfig, ConfigError>
+    {
+        // Limites de DBMS_APPLICATION_INFO e DBMS_SESSION
+        max_bytes("session.module", &self.module, 48)?;
+        max_bytes("session.action", &self.action, 32)?;
+        max_bytes("session.client_identifier", &self.client_identifier, 64)?;
+
+        if let Some(chars) = &self.nls_numeric_characters
+        {
+            let mut it = chars.chars();
+            let valid = matches!((it.next(), it.next(), it.next()), (Some(a), Some(b), None) if a != b);
+            if !valid
+            {
+                return Err(ConfigError::Invalid {
+                    field: "session.nls_numeric_characters",
+                    reason: "use dois caracteres distintos: separador decimal e de milhar (ex.: \".,\")".to_string(),
+                });
+            }
+        }
+        if let Some(schema) = &self.current_schema
+        {
+            quote_identifier(schema).map_err(|e| ConfigError::Invalid {
+                field: "session.current_schema",
+                reason: e.to_string(),
+            })?;
+        }
+        if self.statements.iter().any(|s| s.trim().is_empty())
+        {
+            return Err(ConfigError::Invalid {
+                field: "session.statements",
+                reason: "instrução vazia".to_string(),
+            });
+        }
+
+        Ok(SessionConfig {
+            tag: SessionTag {
+                module: self.module,
+                action: self.action,
+                client_identifier: self.client_identifier,
+            },
+            nls_date_format: self.nls_date_format,
+            nls_timestamp_format: self.nls_timestamp_format,
+            nls_numeric_characters: self.nls_numeric_characters,
+            current_schema: self.current_schema,
+            statements: self
+                .statements
+                .into_iter()
+                .map(|s| s.trim().trim_end_matches(';').to_string())
+                .collect(),
+        })
+    }
+}
+
+impl GuardSettings
+{
+    fn build(self) -> Result<SqlGuard, ConfigError>
+    {
+        let allowed_schemas = self
+            .allowed_schemas
+            .iter()
+            .map(|schema| stored_name(schema))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| ConfigError::Invalid {
+                field: "guard.allowed_schemas",
+                reason: e.to_string(),
+            })?;
+        Ok(SqlGuard {
+            allowed_schemas,
+            read_only_session: self.read_only_session.unwrap_or(false),
+        })
+    }
+}
+
+impl CostLimitSettings
+{
+    fn build(self) -> Result<CostLimit, ConfigError>
+    {
+        let max_cost = self.max_cost.ok_or(ConfigError::Missing {
+            field: "cost_limit.max_cost",
+        })?;
+        if max_cost == 0
+        {
+            return Err(ConfigError::Invalid {
+                field: "cost_limit.max_cost",
+                reason: "deve ser maior que zero".to_string(),
+            });
+        }
+        Ok(CostLimit {
+            max_cost,
+            action: self.action.unwrap_or_default(),
+        })
+    }
+}
+
+fn max_bytes(field: &'static str, value: &Option<String>, max: usize) -> Result<(), ConfigError>
+{
+    match value
+    {
+        Some(value) if value.len() > max => Err(ConfigError::Invalid {
+            field,
+            reason: format!("no máximo {} bytes ({} informados)", max, value.len()),
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn required(field: &'static str, value: Option<String>) -> Result<String, ConfigError>
+{
+    non_empty(field, value.ok_or(ConfigError::Missing { field })?)
+}
+
+fn non_empty(field: &'static str, value: String) -> Result<String, ConfigError>
+{
+    let value = value.trim().to_string();
+    if value.is_empty()
+    {
+        return Err(ConfigError::Missing { field });
+    }
+    Ok(value)
+}
+
+/// Lê uma variável de ambiente em segundos e a converte para milissegundos.
+fn env_secs_as_ms(key: &'static str) -> Result<Option<u64>, ConfigError>
+{
+    Ok(env_parse::<u64>(key)?.map(|secs| secs.saturating_mul(1000)))
+}
+
+/// Duração em milissegundos, saturando em `u64::MAX`.
+fn millis(duration: Duration) -> u64
+{
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Lê uma variável de ambiente, tratando valores vazios como ausentes.
+fn env_string(key: &str) -> Option<String>
+{
+    env::var(key).ok().filter(|v| !v.trim().is_empty())
+}
+
+/// Lê e converte uma variável de ambiente opcional.
+fn env_parse<T>(key: &'static str) -> Result<Option<T>, ConfigError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    env_string(key)
+        .map(|value| {
+            value.trim().parse().map_err(|e: T::Err| ConfigError::Invalid {
+                field: key,
+                reason: e.to_string(),
+            })
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    const BASE: &str = "username = \"scott\"\npassword = \"tiger\"\nhost = \"db\"\nservice_name = \"ORCL\"\n";
+
+    fn invalid_field(toml: &str) -> &'static str
+    {
+        match OracleConfig::from_toml_str(&format!("{}{}", BASE, toml))
+        {
+            Err(ConfigError::Invalid { field, .. }) => field,
+            other => panic!("esperado ConfigError::Invalid, veio {:?}", other),
+        }
+    }
+
+    #[test]
+    fn builds_connect_string_from_host_and_service()
+    {
+        let config = OracleConfig::from_toml_str(BASE).unwrap();
+        assert_eq!(config.connect_string, "//db:1521/ORCL");
+        assert!(config.pool.is_none());
+        assert!(config.call_timeout.is_none());
+
+        let config = OracleConfig::builder()
+            .username("scott")
+            .password("tiger")
+            .dsn("PROD")
+            .build()
+            .unwrap();
+        assert_eq!(config.connect_string, "PROD");
+    }
+
+    #[test]
+    fn reports_missing_fields()
+    {
+        let missing = OracleConfig::from_toml_str("username = \"scott\"\npassword = \"tiger\"\nhost = \"db\"\n");
+        assert!(matches!(missing, Err(ConfigError::Missing { field: "service_name" })));
+
+        let missing = OracleConfig::builder().username("scott").dsn("PROD").build();
+        assert!(matches!(missing, Err(ConfigError::Missing { field: "password" })));
+
+        let missing = OracleConfig::from_toml_str(&format!("{}[cost_limit]\naction = \"warn\"\n", BASE));
+        assert!(matches!(
+            missing,
+            Err(ConfigError::Missing {
+                field: "cost_limit.max_cost"
+            })
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_values()
+    {
+        assert_eq!(invalid_field("port = 0\n"), "port");
+        assert_eq!(invalid_field("call_timeout_ms = 0\n"), "call_timeout_ms");
+        assert_eq!(invalid_field("client_path = \"/nao/existe/instantclient\"\n"), "client_path");
+        assert_eq!(invalid_field("[pool]\nmax_connections = 0\n"), "pool.max_connections");
+        assert_eq!(
+            invalid_field("[pool]\nmin_connections = 5\nmax_connections = 2\n"),
+            "pool.min_connections"
+        );
+        assert_eq!(invalid_field("[retry]\nmax_attempts = 0\n"), "retry.max_attempts");
+        assert_eq!(
+            invalid_field("[retry]\ninitial_backoff_ms = 5000\nmax_backoff_ms = 100\n"),
+            "retry.initial_backoff_ms"
+        );
+        assert_eq!(invalid_field("[retry]\njitter = 1.5\n"), "retry.jitter");
+        assert_eq!(invalid_field("[retry]\nmultiplier = 0.5\n"), "retry.multiplier");
+        assert_eq!(
+            invalid_field(&format!("[session]\nmodule = \"{}\"\n", "m".repeat(49))),
+            "session.module"
+        );
+        assert_eq!(
+            invalid_field(&format!("[session]\naction = \"{}\"\n", "a".repeat(33))),
+            "session.action"
+        );
+        assert_eq!(
+            invalid_field(&format!("[session]\nclient_identifier = \"{}\"\n", "c".repeat(65))),
+            "session.client_identifier"
+        );
+        assert_eq!(
+            invalid_field("[session]\nnls_numeric_characters = \",,\"\n"),
+            "session.nls_numeric_characters"
+        );
+        assert_eq!(invalid_field("[session]\nstatements = [\"  \"]\n"), "session.statements");
+        assert_eq!(
+            invalid_field("[guard]\nread_only_session = true\n"),
+            "guard.read_only_session"
+        );
+        assert_eq!(invalid_field("[cost_limit]\nmax_cost = 0\n"), "cost_limit.max_cost");
+
+        let dsn = OracleConfig::builder()
+            .username("scott")
+            .password("tiger")
+            .dsn("PROD DB")
+            .build();
+        assert!(matches!(dsn, Err(ConfigError::Invalid { field: "dsn", .. })));
+    }
+
+    #[test]
+    fn rejects_unknown_fields()
+    {
+        let unknown = OracleConfig::from_toml_str(&format!("{}[pool]\nping_interval_ms = 10\n", BASE));
+        assert!(matches!(unknown, Err(ConfigError::Toml(_))));
+    }
+
+    #[test]
+    fn pool_times_are_whole_seconds_and_zero_disables_ping()
+    {
+        let config = OracleConfig::from_toml_str(&format!(
+            "{}call_timeout_ms = 250\n[pool]\nidle_timeout_secs = 2\nping_interval_secs = 0\n",
+            BASE
+        ))
+        .unwrap();
+        let pool = config.pool.unwrap();
+        assert_eq!(pool.idle_timeout, Duration::from_secs(2));
+        assert_eq!(pool.ping_interval, None);
+        assert_eq!(config.call_timeout, Some(Duration::from_millis(250)));
+
+        let config = OracleConfig::from_toml_str(&format!("{}[pool]\n", BASE)).unwrap();
+        assert_eq!(config.pool.unwrap().ping_interval, PoolConfig::default().ping_interval);
+    }
+
+    #[test]
+    fn builder_rounds_sub_second_pool_times_up()
+    {
+        let config = OracleConfig::builder()
+            .dsn("host/svc")
+            .username("u")
+            .password("p")
+            .pool(PoolConfig {
+                idle_timeout: Duration::from_millis(500),
+                ping_interval: Some(Duration::ZERO),
+                ..PoolConfig::default()
+            })
+            .build()
+            .unwrap();
+        let pool = config.pool.unwrap();
+        assert_eq!(pool.idle_timeout, Duration::from_secs(1));
+        assert_eq!(pool.ping_interval, Some(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn session_statements_lose_the_trailing_semicolon()
+    {
+        let config = OracleConfig::from_toml_str(&format!(
+            "{}[session]\nstatements = [\"ALTER SESSION SET TIME_ZONE = 'UTC';\"]\n",
+            BASE
+        ))
+        .unwrap();
+        assert_eq!(config.session.statements, vec!["ALTER SESSION SET TIME_ZONE = 'UTC'"]);
+    }
+
+    #[test]
+    fn retry_multiplier_reaches_the_policy()
+    {
+        let config = OracleConfig::from_toml_str(&format!("{}[retry]\nmultiplier = 3.0\n", BASE)).unwrap();
+        assert_eq!(config.retry.multiplier, 3.0);
+
+        let retry = RetryPolicy {
+            multiplier: 1.5,
+            jitter: 0.0,
+            ..RetryPolicy::default()
+        };
+        let config = OracleConfig::builder()
+            .username("scott")
+            .password("tiger")
+            .dsn("PROD")
+            .retry(retry.clone())
+            .build()
+            .unwrap();
+        assert_eq!(config.retry, retry);
+        // 200 ms * 1.5²
+        assert_eq!(config.retry.backoff(3), Duration::from_millis(450));
+    }
+}
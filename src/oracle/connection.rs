@@ -1,106 +1,659 @@
-use std::env;
+use std::sync::Arc;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
 
-use dotenv::dotenv;
 use log::info;
+use log::warn;
+use oracle::conn::CloseMode;
+use oracle::pool::Pool;
 use oracle::sql_type::ToSql;
+use oracle::ConnStatus;
 use oracle::Connection;
-use oracle::Row;
+use oracle::InitParams;
 use polars::prelude::*;
+use tokio::task;
 
-/// EngineOracle encapsula a conexão e execução de queries no Oracle,
+use super::cancel::run_guarded;
+use super::cancel::CancelOnDrop;
+use super::cancel::CancelToken;
+use super::config::OracleConfig;
+use super::engine::DataFrameChunks;
+use super::engine::SqlEngine;
+use super::engine::SqlValue;
+use super::error::EngineError;
+use super::error::EngineResult;
+use super::guard::read_only_error;
+use super::guard::SqlGuard;
+use super::options::QueryOptions;
+use super::plan::enforce_cost_limit;
+use super::plan::CostLimit;
+use super::pool::EngineConnection;
+use super::pool::PoolConfig;
+use super::pool::PoolStats;
+use super::pool::SessionLock;
+use super::retry::RetryPolicy;
+use super::session::SessionConfig;
+use super::session::SessionTag;
+use super::stream::fetch_chunk;
+use super::stream::open_result_set;
+use super::stream::result_schema;
+use super::stream::DataFrameStream;
+use super::transaction::IsolationLevel;
+use super::transaction::Transaction;
+use super::write::write_df;
+use super::write::WriteMode;
+
+/// Parâmetros posicionais com posse, para consultas executadas fora da thread chamadora.
+pub type OwnedParams = Vec<Box<dyn ToSql + Send + Sync>>;
+
+/// EngineOracle encapsula a conexão (ou o pool de conexões) e a execução de queries no Oracle,
 /// retornando resultados em um DataFrame do Polars.
 pub struct EngineOracle
 {
-    conn: Connection,
+    backend: Backend,
+    /// Timeout aplicado às consultas que não definem `QueryOptions::timeout`.
+    default_timeout: Option<Duration>,
+    /// Novas tentativas das leituras que falham por perda de conexão.
+    retry: RetryPolicy,
+    /// Identificação padrão e instruções executadas em cada conexão nova.
+    session: SessionConfig,
+    /// Modo somente leitura: confere cada consulta e recusa as escritas.
+    guard: Option<SqlGuard>,
+    /// Limite de custo usado quando `QueryOptions::cost_limit` não define um.
+    cost_limit: Option<CostLimit>,
+}
+
+/// Origem das conexões usadas pelo engine.
+enum Backend
+{
+    /// Conexão única, trocada por uma nova quando cai. Cada empréstimo reserva a sessão
+    /// (`session`) até ser descartado.
+    Single
+    {
+        conn: RwLock<Arc<Connection>>,
+        session: Arc<SessionLock>,
+        credentials: Credentials,
+    },
+    Pooled
+    {
+        pool: Pool, config: PoolConfig
+    },
+}
+
+/// Dados para reabrir a conexão única.
+struct Credentials
+{
+    username: String,
+    password: String,
+    connect_string: String,
+}
+
+impl Credentials
+{
+    fn connect(&self) -> EngineResult<Connection>
+    {
+        Connection::connect(&self.username, &self.password, &self.connect_string).map_err(EngineError::connect)
+    }
 }
 
 impl EngineOracle
 {
-    /// Cria um novo EngineOracle, carregando configurações do ambiente e abrindo a conexão.
-    pub fn new() -> Result<Self, Box<dyn std::error::Error + Send + Sync>>
-    {
-        // Inicializa variáveis de ambiente e logger
-        dotenv().ok();
-        env_logger::init();
-
-        // Lê credenciais e detalhes de conexão
-        let username = env::var("ORACLE_USERNAME")?;
-        let password = env::var("ORACLE_PASSWORD")?;
-        let host = env::var("ORACLE_HOST")?;
-        let port = env::var("ORACLE_PORT")?;
-        let service_name = env::var("ORACLE_SERVICE_NAME")?;
-
-        // Caso fornecido, ajusta o caminho do client Oracle
-        if let Ok(client_path) = env::var("CLIENT_PATH")
+    /// Cria um novo EngineOracle a partir das variáveis de ambiente (`OracleConfig::from_env`).
+    ///
+    /// Não carrega o `.env` nem inicializa o logger: isso fica a cargo do binário.
+    pub fn new() -> EngineResult<Self>
+    {
+        Self::from_config(&OracleConfig::from_env()?)
+    }
+
+    /// Cria um EngineOracle com conexão única ou pool, conforme `config.pool`.
+    pub fn from_config(config: &OracleConfig) -> EngineResult<Self>
+    {
+        // Caso fornecido, inicializa o driver com o caminho do client Oracle
+        if let Some(client_path) = &config.client_path
         {
-            env::set_var("LD_LIBRARY_PATH", &client_path);
-            info!("Oracle Client configurado a partir de: {}", client_path);
+            let initialized = InitParams::new()
+                .oracle_client_lib_dir(client_path)
+                .and_then(|params| params.init())
+                .map_err(EngineError::connect)?;
+            if initialized
+            {
+                info!("Oracle Client configurado a partir de: {}", client_path.display());
+            }
+            else
+            {
+                info!("Oracle Client já inicializado; client_path ignorado.");
+            }
         }
         else
         {
-            info!("CLIENT_PATH não definido; usando configuração padrão do sistema.");
+            info!("client_path não definido; usando configuração padrão do sistema.");
         }
 
-        // Monta a string de conexão: //host:port/service_name
-        let connect_string = format!("//{}:{}/{}", host, port, service_name);
-        info!("Conectando ao Oracle em: {}", connect_string);
+        info!("Conectando ao Oracle em: {}", config.connect_string);
 
-        // Estabelece a conexão
-        let conn = Connection::connect(&username, &password, &connect_string)?;
-        info!("Conexão estabelecida com sucesso!");
+        let backend = match &config.pool
+        {
+            Some(pool_config) =>
+            {
+                let pool = pool_config.build(&config.username, &config.password, &config.connect_string)?;
+                info!(
+                    "Pool criado com sucesso ({}..{} conexões).",
+                    pool_config.min_connections, pool_config.max_connections
+                );
+                Backend::Pooled {
+                    pool,
+                    config: pool_config.clone(),
+                }
+            },
+            None =>
+            {
+                let credentials = Credentials {
+                    username: config.username.clone(),
+                    password: config.password.clone(),
+                    connect_string: config.connect_string.clone(),
+                };
+                let conn = credentials.connect()?;
+                config.session.init(&conn)?;
+                info!("Conexão estabelecida com sucesso!");
+                Backend::Single {
+                    conn: RwLock::new(Arc::new(conn)),
+                    session: Arc::default(),
+                    credentials,
+                }
+            },
+        };
 
-        Ok(Self { conn })
+        Ok(Self {
+            backend,
+            default_timeout: config.call_timeout,
+            retry: config.retry.clone(),
+            session: config.session.clone(),
+            guard: config.guard.clone(),
+            cost_limit: config.cost_limit,
+        })
     }
 
-    /// Executa uma query segura no Oracle e converte o resultado em DataFrame.
+    /// Empresta uma conexão: a conexão única ou uma conexão do pool, validada na retirada
+    /// e devolvida automaticamente quando o `EngineConnection` é descartado.
+    ///
+    /// Se a conexão única estiver marcada como encerrada pelo driver, reconecta antes. A sessão
+    /// recebe a identificação padrão da configuração (veja `connection_with`).
+    pub fn connection(&self) -> EngineResult<EngineConnection>
+    {
+        self.connection_with(&SessionTag::default())
+    }
+
+    /// Como `connection`, com `MODULE`, `ACTION` e `CLIENT_IDENTIFIER` de `tag` (os campos
+    /// vazios usam os da configuração). Sessões recém-criadas pelo pool recebem antes os
+    /// ajustes de `SessionConfig`.
+    ///
+    /// Na conexão única, o `EngineConnection` tem uso exclusivo da sessão enquanto existir:
+    /// outras threads esperam ele ser descartado e a mesma thread recebe
+    /// `EngineError::InvalidArgument`. Use o pool para consultas em paralelo.
+    pub fn connection_with(&self, tag: &SessionTag) -> EngineResult<EngineConnection>
+    {
+        let conn = self.checkout()?;
+        tag.apply(&conn, &self.session.tag)?;
+        Ok(conn)
+    }
+
+    fn checkout(&self) -> EngineResult<EngineConnection>
+    {
+        match &self.backend
+        {
+            Backend::Single { conn, session, .. } =>
+            {
+                let guard = session.acquire()?;
+                let current = conn.read().unwrap_or_else(PoisonError::into_inner).clone();
+                if matches!(current.status(), Ok(ConnStatus::Normal))
+                {
+                    Ok(EngineConnection::Single(current, guard))
+                }
+                else
+                {
+                    Ok(EngineConnection::Single(self.reconnect(&current)?, guard))
+                }
+            },
+            Backend::Pooled { pool, .. } =>
+            {
+                let conn = pool.get()?;
+                if conn.is_new_connection()
+                {
+                    if let Err(e) = self.session.init(&conn)
+                    {
+                        // Devolvida ao pool, a sessão voltaria sem os ajustes e não seria
+                        // mais "nova"; descarta-a para a próxima retirada criar outra
+                        if let Err(close) = conn.close_with_mode(CloseMode::Drop)
+                        {
+                            warn!("Falha ao descartar a sessão não inicializada: {}", close);
+                        }
+                        return Err(e);
+                    }
+                }
+                Ok(EngineConnection::Pooled(conn))
+            },
+        }
+    }
+
+    /// Verifica se o banco responde, com um round-trip (`ping`), e devolve a latência.
+    ///
+    /// Segue a política de novas tentativas, reconectando se a conexão tiver caído.
+    pub fn ping(&self) -> EngineResult<Duration>
+    {
+        self.with_retry("ping", &SessionTag::default(), |conn| {
+            let started = Instant::now();
+            conn.ping()?;
+            Ok(started.elapsed())
+        })
+    }
+
+    /// Guard do modo somente leitura, quando configurado (`OracleConfig::guard`).
+    pub fn guard(&self) -> Option<&SqlGuard>
+    {
+        self.guard.as_ref()
+    }
+
+    /// Estatísticas do pool (`None` quando o engine usa conexão única).
+    pub fn pool_stats(&self) -> EngineResult<Option<PoolStats>>
+    {
+        match &self.backend
+        {
+            Backend::Single { .. } => Ok(None),
+            Backend::Pooled { pool, config } => Ok(Some(PoolStats {
+                open: pool.open_count()?,
+                busy: pool.busy_count()?,
+                min: config.min_connections,
+                max: config.max_connections,
+            })),
+        }
+    }
+
+    /// Executa uma query segura no Oracle e converte o resultado em DataFrame com colunas tipadas.
     ///
     /// # Parâmetros
     /// - `sql`: instrução SQL com binds posicionais (`:1`, `:2`, ...)
     /// - `params`: slice de parâmetros que implementam `ToSql`
-    pub fn query_to_polars_df(
+    pub fn query_to_polars_df(&self, sql: &str, params: &[&dyn ToSql]) -> EngineResult<DataFrame>
+    {
+        self.query_to_polars_df_with(sql, params, &QueryOptions::default())
+    }
+
+    /// Igual a `query_to_polars_df`, mas com opções de conversão explícitas.
+    ///
+    /// Use `FetchMode::Strings` para obter o comportamento antigo (todas as colunas como `String`).
+    pub fn query_to_polars_df_with(
+        &self,
+        sql: &str,
+        params: &[&dyn ToSql],
+        options: &QueryOptions,
+    ) -> EngineResult<DataFrame>
+    {
+        self.fetch_df(sql, params, options, None).map_err(|e| e.with_sql(sql))
+    }
+
+    /// Igual a `query_to_polars_df_with`, interrompendo a consulta no servidor quando `cancel`
+    /// for acionado (de outra thread, por exemplo).
+    pub fn query_to_polars_df_cancellable(
+        &self,
+        sql: &str,
+        params: &[&dyn ToSql],
+        options: &QueryOptions,
+        cancel: &CancelToken,
+    ) -> EngineResult<DataFrame>
+    {
+        self.fetch_df(sql, params, options, Some(cancel)).map_err(|e| e.with_sql(sql))
+    }
+
+    /// Versão assíncrona de `query_to_polars_df_with`: o fetch roda em uma thread de bloqueio
+    /// (`spawn_blocking`), sem ocupar os workers do runtime Tokio.
+    ///
+    /// Se o future for descartado antes de terminar (cliente HTTP desconectou), o fetch é
+    /// interrompido na próxima linha lida.
+    pub async fn query_to_polars_df_async(
+        self: Arc<Self>,
+        sql: impl Into<String>,
+        params: OwnedParams,
+        options: QueryOptions,
+    ) -> EngineResult<DataFrame>
+    {
+        let sql = sql.into();
+        let cancel = CancelToken::new();
+        let _guard = CancelOnDrop(cancel.clone());
+
+        task::spawn_blocking(move || {
+            let params: Vec<&dyn ToSql> = params.iter().map(|p| p.as_ref() as &dyn ToSql).collect();
+            self.fetch_df(&sql, &params, &options, Some(&cancel))
+                .map_err(|e| e.with_sql(&sql))
+        })
+        .await?
+    }
+
+    /// Executa a query e devolve um iterador de DataFrames com até `chunk_rows` linhas cada,
+    /// lidos sob demanda. Use `QueryOptions::fetch_array_size` e `QueryOptions::prefetch_rows`
+    /// para ajustar quantas linhas vêm do servidor a cada round-trip.
+    ///
+    /// O timeout vale para cada round-trip, não para a leitura inteira, que depende do consumidor.
+    pub fn query_to_polars_df_stream(
+        &self,
+        sql: &str,
+        params: &[&dyn ToSql],
+        chunk_rows: usize,
+        options: &QueryOptions,
+    ) -> EngineResult<DataFrameStream>
+    {
+        self.check_read(sql).map_err(|e| e.with_sql(sql))?;
+        // Só a abertura é repetida: depois do primeiro bloco entregue, a leitura não recomeça.
+        // A transação somente leitura termina no rollback do pool, quando o stream é descartado.
+        self.with_retry("abertura do stream", &options.session, |conn| {
+            self.check_cost(&conn, sql, params, options)?;
+            self.begin_read_only(&conn)?;
+            DataFrameStream::new(conn, sql, params, chunk_rows, options, self.timeout_for(options))
+        })
+        .map_err(|e| e.with_sql(sql))
+    }
+
+    /// Grava o DataFrame na tabela usando array binds, conforme o `WriteMode`.
+    ///
+    /// Os tipos dos binds vêm do dtype de cada coluna; colunas `String` (como as lidas do Excel)
+    /// são convertidas pelo próprio Oracle para o tipo da coluna de destino. Faz commit ao final
+    /// e rollback em caso de erro. Retorna o número de linhas gravadas.
+    pub fn write_polars_df(&self, table: &str, df: &DataFrame, mode: WriteMode) -> EngineResult<usize>
+    {
+        self.check_write("write_polars_df")?;
+        let conn = self.connection()?;
+        match write_df(&conn, table, df, &mode)
+        {
+            Ok(rows) =>
+            {
+                conn.commit()?;
+                Ok(rows)
+            },
+            Err(e) =>
+            {
+                conn.rollback()?;
+                Err(e)
+            },
+        }
+    }
+
+    /// Abre uma transação com o isolamento padrão (`READ COMMITTED`).
+    ///
+    /// Na conexão única, a transação tem uso exclusivo da sessão até o commit ou rollback
+    /// (veja `Transaction`).
+    pub fn begin(&self) -> EngineResult<Transaction>
+    {
+        self.begin_with(IsolationLevel::default())
+    }
+
+    /// Abre uma transação com o nível de isolamento informado.
+    pub fn begin_with(&self, isolation: IsolationLevel) -> EngineResult<Transaction>
+    {
+        self.check_write("transação")?;
+        Transaction::begin(self.connection()?, isolation)
+    }
+
+    /// Executa `f` dentro de uma transação: commit se retornar `Ok`, rollback se retornar
+    /// `Err` ou entrar em panic. Dentro de `f`, use `tx` e não o engine: na conexão única a
+    /// sessão está reservada para a transação.
+    ///
+    /// ```ignore
+    /// engine.transaction(IsolationLevel::ReadCommitted, |tx| {
+    ///     tx.execute("DELETE FROM STAGING WHERE LOTE = :1", &[&lote])?;
+    ///     tx.write_polars_df("STAGING", &df, WriteMode::Append)
+    /// })?;
+    /// ```
+    pub fn transaction<T>(
+        &self,
+        isolation: IsolationLevel,
+        f: impl FnOnce(&Transaction) -> EngineResult<T>,
+    ) -> EngineResult<T>
+    {
+        let tx = self.begin_with(isolation)?;
+        let value = f(&tx)?;
+        tx.commit()?;
+        Ok(value)
+    }
+
+    fn fetch_df(
         &self,
         sql: &str,
         params: &[&dyn ToSql],
-    ) -> Result<DataFrame, Box<dyn std::error::Error + Send + Sync>>
+        options: &QueryOptions,
+        cancel: Option<&CancelToken>,
+    ) -> EngineResult<DataFrame>
     {
-        // Executa a query e obtém um ResultSet<Row>
-        let mut rows = self.conn.query(sql, params)?;
+        self.check_read(sql)?;
+        self.with_retry("consulta", &options.session, |conn| {
+            self.check_cost(&conn, sql, params, options)?;
+            self.begin_read_only(&conn)?;
+            let result = run_guarded(&conn, cancel, self.timeout_for(options), |token| {
+                // Executa a query e obtém um ResultSet<Row>
+                let mut rows = open_result_set(&conn, sql, params, options)?;
 
-        // Metadata das colunas
-        let column_info = rows.column_info();
-        let column_count = column_info.len();
-        let column_names: Vec<String> = column_info.iter().map(|ci| ci.name().to_string()).collect();
+                // Metadata das colunas e tipo Polars de destino de cada uma
+                let schema = result_schema(&rows, options);
+                info!("Colunas encontradas: {:?}", schema.names);
 
-        info!("Colunas encontradas: {:?}", column_names);
+                // Lê todas as linhas de uma vez
+                let df = fetch_chunk(&mut rows, &schema, None, 0, Some(token))?;
+                info!("Consulta executada; {} linhas recuperadas.", df.height());
 
-        // Preparar armazenamento de dados por coluna
-        let mut data: Vec<Vec<Option<String>>> = vec![Vec::new(); column_count];
+                Ok(df)
+            });
+            // Em caso de erro, o rollback do pool encerra a transação somente leitura
+            result.and_then(|df| self.end_read_only(&conn).map(|_| df))
+        })
+    }
 
-        // Itera sobre cada linha retornada
-        while let Some(row_res) = rows.next()
+    /// Executa `op` com uma conexão emprestada, repetindo conforme a política de novas tentativas
+    /// quando a falha for perda de conexão. Use apenas com operações idempotentes (leituras).
+    fn with_retry<T>(
+        &self,
+        what: &str,
+        tag: &SessionTag,
+        mut op: impl FnMut(EngineConnection) -> EngineResult<T>,
+    ) -> EngineResult<T>
+    {
+        let mut attempt = 1;
+        loop
         {
-            let row: Row = row_res?;
-            for i in 0..column_count
+            let result = self.connection_with(tag).and_then(|conn| {
+                let single = match &conn
+                {
+                    EngineConnection::Single(conn, _) => Some(Arc::clone(conn)),
+                    EngineConnection::Pooled(_) => None,
+                };
+                op(conn).inspect_err(|e| {
+                    // Troca a conexão única já aqui; conexões do pool marcadas como mortas
+                    // são descartadas pelo driver ao voltarem ao pool
+                    if let (true, Some(stale)) = (e.is_connection_lost(), &single)
+                    {
+                        if let Err(e) = self.reconnect(stale)
+                        {
+                            warn!("Falha ao reconectar ao Oracle: {}", e);
+                        }
+                    }
+                })
+            });
+
+            match result
             {
-                let value: Option<String> = row.get(i)?;
-                data[i].push(value);
+                Err(e) if e.is_connection_lost() && attempt < self.retry.max_attempts =>
+                {
+                    let delay = self.retry.backoff(attempt);
+                    attempt += 1;
+                    warn!(
+                        "{} falhou por perda de conexão ({}); tentativa {}/{} em {:?}.",
+                        what, e, attempt, self.retry.max_attempts, delay
+                    );
+                    thread::sleep(delay);
+                },
+                result => return result,
             }
         }
+    }
 
-        info!(
-            "Consulta executada; {} linhas recuperadas.",
-            if data.is_empty() { 0 } else { data[0].len() }
-        );
+    /// Substitui a conexão única por uma nova, se ela ainda for `stale` (outra thread pode ter
+    /// reconectado antes), e devolve a conexão em vigor.
+    fn reconnect(&self, stale: &Arc<Connection>) -> EngineResult<Arc<Connection>>
+    {
+        let Backend::Single { conn, credentials, .. } = &self.backend
+        else
+        {
+            return Err(EngineError::InvalidArgument(
+                "reconexão só se aplica à conexão única".to_string(),
+            ));
+        };
 
-        // Cria Series e DataFrame
-        let series_vec: Vec<Series> = column_names
-            .iter()
-            .enumerate()
-            .map(|(i, name)| Series::new(name.into(), &data[i]))
-            .collect();
+        let mut current = conn.write().unwrap_or_else(PoisonError::into_inner);
+        if Arc::ptr_eq(&current, stale)
+        {
+            warn!(
+                "Conexão com o Oracle perdida; reconectando em {}.",
+                credentials.connect_string
+            );
+            let fresh = credentials.connect()?;
+            self.session.init(&fresh)?;
+            *current = Arc::new(fresh);
+            info!("Reconectado ao Oracle.");
+        }
+        Ok(Arc::clone(&current))
+    }
+
+    /// Confere a consulta no guard, quando o engine está em modo somente leitura.
+    pub(crate) fn check_read(&self, sql: &str) -> EngineResult<()>
+    {
+        match &self.guard
+        {
+            Some(guard) => guard.check(sql),
+            None => Ok(()),
+        }
+    }
+
+    /// Recusa a operação de escrita quando o engine está em modo somente leitura.
+    pub(crate) fn check_write(&self, operation: &str) -> EngineResult<()>
+    {
+        match &self.guard
+        {
+            Some(_) => Err(read_only_error(operation)),
+            None => Ok(()),
+        }
+    }
+
+    /// Confere o custo do plano contra o limite das opções ou do engine, se houver.
+    fn check_cost(&self, conn: &Connection, sql: &str, params: &[&dyn ToSql], options: &QueryOptions) -> EngineResult<()>
+    {
+        match options.cost_limit.as_ref().or(self.cost_limit.as_ref())
+        {
+            Some(limit) => enforce_cost_limit(conn, sql, params, limit),
+            None => Ok(()),
+        }
+    }
 
-        let df = DataFrame::new(series_vec.into_iter().map(|s| s.into_column()).collect())?;
-        Ok(df)
+    fn begin_read_only(&self, conn: &Connection) -> EngineResult<()>
+    {
+        match &self.guard
+        {
+            Some(guard) => guard.begin_read_only(conn),
+            None => Ok(()),
+        }
+    }
+
+    fn end_read_only(&self, conn: &Connection) -> EngineResult<()>
+    {
+        match &self.guard
+        {
+            Some(guard) => guard.end_read_only(conn),
+            None => Ok(()),
+        }
+    }
+
+    /// Timeout da consulta: o das opções ou, na falta dele, o padrão do engine.
+    pub(crate) fn timeout_for(&self, options: &QueryOptions) -> Option<Duration>
+    {
+        options.timeout.or(self.default_timeout)
+    }
+}
+
+impl SqlEngine for EngineOracle
+{
+    fn backend_name(&self) -> &'static str
+    {
+        "oracle"
+    }
+
+    fn query_df(
+        &self,
+        sql: &str,
+        params: &[SqlValue],
+        options: &QueryOptions,
+        cancel: Option<&CancelToken>,
+    ) -> EngineResult<DataFrame>
+    {
+        self.fetch_df(sql, &oracle_params(params), options, cancel)
+            .map_err(|e| e.with_sql(sql))
+    }
+
+    fn execute(&self, sql: &str, params: &[SqlValue]) -> EngineResult<u64>
+    {
+        self.check_write("execute")?;
+        let conn = self.connection()?;
+        let stmt = conn
+            .execute(sql, &oracle_params(params))
+            .map_err(|e| EngineError::from(e).with_sql(sql))?;
+        let rows = stmt.row_count()?;
+        conn.commit()?;
+        Ok(rows)
+    }
+
+    fn query_df_stream<'a>(
+        &'a self,
+        sql: &str,
+        params: &[SqlValue],
+        chunk_rows: usize,
+        options: &QueryOptions,
+    ) -> EngineResult<DataFrameChunks<'a>>
+    {
+        let stream = self.query_to_polars_df_stream(sql, &oracle_params(params), chunk_rows, options)?;
+        Ok(Box::new(stream))
+    }
+
+    fn ping(&self) -> EngineResult<Duration>
+    {
+        EngineOracle::ping(self)
+    }
+
+    fn pool_stats(&self) -> EngineResult<Option<PoolStats>>
+    {
+        EngineOracle::pool_stats(self)
+    }
+}
+
+/// Converte os binds genéricos para os tipos aceitos pelo driver Oracle.
+fn oracle_params(params: &[SqlValue]) -> Vec<&dyn ToSql>
+{
+    params.iter().map(oracle_value).collect()
+}
+
+/// Bind do driver Oracle para um valor genérico.
+///
+/// `Bool` vira `1`/`0`, já que o tipo BOOLEAN só existe a partir do Oracle 23ai.
+pub(crate) fn oracle_value(param: &SqlValue) -> &dyn ToSql
+{
+    match param
+    {
+        SqlValue::Null => &None::<String>,
+        SqlValue::Int(v) => v,
+        SqlValue::Float(v) => v,
+        SqlValue::Text(v) => v,
+        SqlValue::Bool(true) => &1i64,
+        SqlValue::Bool(false) => &0i64,
+        SqlValue::Date(v) => v,
+        SqlValue::Datetime(v) => v,
     }
 }
 
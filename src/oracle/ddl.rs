@@ -0,0 +1,737 @@
+use std::fmt;
+
+use log::info;
+use log::warn;
+use polars::prelude::*;
+
+use super::connection::EngineOracle;
+use super::error::EngineError;
+use super::error::EngineResult;
+use super::sql::quote_identifier;
+use super::sql::quote_table_name;
+use super::sql::split_table_name;
+use super::sql::stored_name;
+
+/// Maior tamanho de identificador aceito pelo Oracle (12.2 em diante).
+const MAX_IDENTIFIER_BYTES: usize = 128;
+
+/// Tipo Oracle de uma coluna gerada a partir do dtype do Polars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType
+{
+    Number
+    {
+        precision: u8,
+        scale: i8,
+    },
+    BinaryFloat,
+    BinaryDouble,
+    /// Tamanho em caracteres (`VARCHAR2(n CHAR)`), para textos com acentos caberem.
+    Varchar2(u32),
+    Clob,
+    Raw(u32),
+    Blob,
+    Date,
+    Timestamp(u8),
+}
+
+impl fmt::Display for ColumnType
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            ColumnType::Number { precision, scale: 0 } => write!(f, "NUMBER({})", precision),
+            ColumnType::Number { precision, scale } => write!(f, "NUMBER({},{})", precision, scale),
+            ColumnType::BinaryFloat => write!(f, "BINARY_FLOAT"),
+            ColumnType::BinaryDouble => write!(f, "BINARY_DOUBLE"),
+            ColumnType::Varchar2(size) => write!(f, "VARCHAR2({} CHAR)", size),
+            ColumnType::Clob => write!(f, "CLOB"),
+            ColumnType::Raw(size) => write!(f, "RAW({})", size),
+            ColumnType::Blob => write!(f, "BLOB"),
+            ColumnType::Date => write!(f, "DATE"),
+            ColumnType::Timestamp(fsprec) => write!(f, "TIMESTAMP({})", fsprec),
+        }
+    }
+}
+
+/// Como dimensionar as colunas de texto.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VarcharSizing
+{
+    /// Mesmo tamanho, em caracteres, para todas as colunas de texto.
+    Fixed(u32),
+    /// Maior texto da coluna multiplicado por `headroom` e arredondado para múltiplos de 10.
+    /// Sem DataFrame (só o `Schema`), usa `DdlOptions::max_varchar`.
+    Observed
+    {
+        headroom: f64
+    },
+    /// Sempre `DdlOptions::max_varchar`.
+    Max,
+}
+
+impl Default for VarcharSizing
+{
+    fn default() -> Self
+    {
+        VarcharSizing::Observed { headroom: 1.5 }
+    }
+}
+
+/// Opções da geração de DDL.
+#[derive(Debug, Clone)]
+pub struct DdlOptions
+{
+    /// Colunas da chave primária, na ordem da constraint (ficam `NOT NULL`).
+    pub primary_key: Vec<String>,
+    /// Colunas `NOT NULL` além das da chave primária.
+    pub not_null: Vec<String>,
+    pub tablespace: Option<String>,
+    pub varchar_sizing: VarcharSizing,
+    /// Maior `VARCHAR2`, em caracteres; textos maiores viram `CLOB` (32767 com `MAX_STRING_SIZE = EXTENDED`).
+    pub max_varchar: u32,
+}
+
+impl Default for DdlOptions
+{
+    fn default() -> Self
+    {
+        Self {
+            primary_key: Vec::new(),
+            not_null: Vec::new(),
+            tablespace: None,
+            varchar_sizing: VarcharSizing::default(),
+            max_varchar: 4000,
+        }
+    }
+}
+
+impl DdlOptions
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    pub fn primary_key<S: Into<String>>(mut self, columns: impl IntoIterator<Item = S>) -> Self
+    {
+        self.primary_key = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn not_null(mut self, column: impl Into<String>) -> Self
+    {
+        self.not_null.push(column.into());
+        self
+    }
+
+    pub fn tablespace(mut self, tablespace: impl Into<String>) -> Self
+    {
+        self.tablespace = Some(tablespace.into());
+        self
+    }
+
+    pub fn varchar_sizing(mut self, sizing: VarcharSizing) -> Self
+    {
+        self.varchar_sizing = sizing;
+        self
+    }
+
+    pub fn max_varchar(mut self, max_varchar: u32) -> Self
+    {
+        self.max_varchar = max_varchar;
+        self
+    }
+
+    fn is_not_null(&self, column: &str) -> bool
+    {
+        self.primary_key
+            .iter()
+            .chain(&self.not_null)
+            .any(|c| c.eq_ignore_ascii_case(column))
+    }
+}
+
+/// Origem das colunas: só o `Schema` ou o DataFrame, que permite medir textos e binários.
+#[derive(Debug, Clone, Copy)]
+pub enum TableSource<'a>
+{
+    Schema(&'a Schema),
+    Frame(&'a DataFrame),
+}
+
+impl<'a> From<&'a Schema> for TableSource<'a>
+{
+    fn from(schema: &'a Schema) -> Self
+    {
+        TableSource::Schema(schema)
+    }
+}
+
+impl<'a> From<&'a DataFrame> for TableSource<'a>
+{
+    fn from(df: &'a DataFrame) -> Self
+    {
+        TableSource::Frame(df)
+    }
+}
+
+impl TableSource<'_>
+{
+    fn columns(&self) -> Vec<(String, DataType)>
+    {
+        match self
+        {
+            TableSource::Schema(schema) => schema.iter().map(|(name, dtype)| (name.to_string(), dtype.clone())).collect(),
+            TableSource::Frame(df) => df
+                .get_columns()
+                .iter()
+                .map(|c| (c.name().to_string(), c.dtype().clone()))
+                .collect(),
+        }
+    }
+
+    /// Maior valor da coluna em caracteres (texto) ou bytes (binário), se houver dados.
+    fn max_length(&self, name: &str) -> EngineResult<Option<usize>>
+    {
+        let TableSource::Frame(df) = self
+        else
+        {
+            return Ok(None);
+        };
+        let series = df.column(name)?.as_materialized_series();
+        let max = match series.dtype()
+        {
+            DataType::Binary => series.binary()?.iter().flatten().map(<[u8]>::len).max(),
+            _ =>
+            {
+                let text = series.cast(&DataType::String)?;
+                text.str()?.iter().flatten().map(|s| s.chars().count()).max()
+            },
+        };
+        Ok(Some(max.unwrap_or(0)))
+    }
+}
+
+/// Tipo Oracle da coluna, conforme o dtype e o dimensionamento das opções.
+///
+/// `Int64` vira `NUMBER(19)` e `UInt64` `NUMBER(20)`, para caber qualquer valor do tipo (o engine
+/// lê essas colunas de volta como `Decimal`); `Boolean` vira `NUMBER(1)`, como `write_polars_df`
+/// grava; `Datetime` com fuso é gravado em UTC.
+pub fn column_type(source: TableSource<'_>, name: &str, dtype: &DataType, options: &DdlOptions) -> EngineResult<ColumnType>
+{
+    let number = |precision| ColumnType::Number { precision, scale: 0 };
+    let column_type = match dtype
+    {
+        DataType::Boolean => number(1),
+        DataType::Int8 | DataType::UInt8 => number(3),
+        DataType::Int16 | DataType::UInt16 => number(5),
+        DataType::Int32 | DataType::UInt32 => number(10),
+        DataType::Int64 => number(19),
+        DataType::UInt64 => number(20),
+        DataType::Float32 => ColumnType::BinaryFloat,
+        DataType::Float64 => ColumnType::BinaryDouble,
+        DataType::Decimal(precision, scale) => ColumnType::Number {
+            precision: precision.unwrap_or(38).min(38) as u8,
+            scale: scale.unwrap_or(0).min(127) as i8,
+        },
+        DataType::Date => ColumnType::Date,
+        DataType::Datetime(unit, _) => ColumnType::Timestamp(match unit
+        {
+            TimeUnit::Milliseconds => 3,
+            TimeUnit::Microseconds => 6,
+            TimeUnit::Nanoseconds => 9,
+        }),
+        DataType::Binary => match source.max_length(name)?
+        {
+            Some(len) if len <= 2000 => ColumnType::Raw(len.max(1) as u32),
+            _ => ColumnType::Blob,
+        },
+        dtype if dtype.is_nested() =>
+        {
+            return Err(EngineError::InvalidArgument(format!(
+                "coluna {}: tipo {} sem equivalente no Oracle",
+                name, dtype
+            )));
+        },
+        _ =>
+        {
+            let size = match options.varchar_sizing
+            {
+                VarcharSizing::Fixed(size) => size as usize,
+                VarcharSizing::Max => options.max_varchar as usize,
+                VarcharSizing::Observed { headroom } => match source.max_length(name)?
+                {
+                    Some(len) => ((len.max(1) as f64 * headroom.max(1.0)).ceil() as usize).div_ceil(10) * 10,
+                    None => options.max_varchar as usize,
+                },
+            };
+            if size > options.max_varchar as usize
+            {
+                ColumnType::Clob
+            }
+            else
+            {
+                ColumnType::Varchar2(size.max(1) as u32)
+            }
+        },
+    };
+    Ok(column_type)
+}
+
+/// `CREATE TABLE` para as colunas da origem, com a chave primária e o tablespace das opções.
+///
+/// ```ignore
+/// let options = DdlOptions::new().primary_key(["Chassi"]).tablespace("STAGING");
+/// let ddl = create_table_sql("SYSADM.STG_CRIPPLE", &df, &options)?;
+/// ```
+pub fn create_table_sql<'a>(table: &str, source: impl Into<TableSource<'a>>, options: &DdlOptions) -> EngineResult<String>
+{
+    let source = source.into();
+    let columns = source.columns();
+    if columns.is_empty()
+    {
+        return Err(EngineError::InvalidArgument("nenhuma coluna para criar a tabela".to_string()));
+    }
+
+    let mut lines = columns
+        .iter()
+        .map(|(name, dtype)| {
+            let column_type = column_type(source, name, dtype, options)?;
+            let not_null = if options.is_not_null(name) { " NOT NULL" } else { "" };
+            Ok(format!("    {} {}{}", quote_identifier(name)?, column_type, not_null))
+        })
+        .collect::<EngineResult<Vec<String>>>()?;
+
+    if !options.primary_key.is_empty()
+    {
+        let keys = options
+            .primary_key
+            .iter()
+            .map(|key| {
+                columns
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(key))
+                    .ok_or_else(|| EngineError::InvalidArgument(format!("coluna da chave primária não encontrada: {}", key)))
+                    .and_then(|(name, _)| quote_identifier(name))
+            })
+            .collect::<EngineResult<Vec<String>>>()?;
+        lines.push(format!(
+            "    CONSTRAINT {} PRIMARY KEY ({})",
+            primary_key_name(table)?,
+            keys.join(", ")
+        ));
+    }
+
+    let mut sql = format!("CREATE TABLE {} (\n{}\n)", quote_table_name(table)?, lines.join(",\n"));
+    if let Some(tablespace) = &options.tablespace
+    {
+        sql.push_str(&format!("\nTABLESPACE {}", quote_identifier(tablespace)?));
+    }
+    Ok(sql)
+}
+
+/// Nome da constraint da chave primária: `PK_` seguido do nome da tabela.
+fn primary_key_name(table: &str) -> EngineResult<String>
+{
+    let (_, name) = split_table_name(table)?;
+    let mut name = format!("PK_{}", name);
+    while name.len() > MAX_IDENTIFIER_BYTES
+    {
+        name.pop();
+    }
+    quote_identifier(&name)
+}
+
+/// Diferenças entre a origem e a tabela existente.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaDrift
+{
+    /// `CREATE TABLE` (se a tabela não existe) ou `ALTER TABLE ... ADD/MODIFY`, na ordem.
+    pub statements: Vec<String>,
+    /// Colunas cujo tipo não pode ser ajustado sem recriar a coluna; não geram instrução.
+    pub conflicts: Vec<String>,
+}
+
+impl SchemaDrift
+{
+    pub fn is_empty(&self) -> bool
+    {
+        self.statements.is_empty() && self.conflicts.is_empty()
+    }
+}
+
+/// Compara a origem com as colunas existentes (o DataFrame de `EngineOracle::list_columns`) e
+/// gera os ALTERs que faltam: `ADD` para colunas novas e `MODIFY` para ampliar tamanho,
+/// precisão ou escala. Nunca reduz nem remove colunas.
+///
+/// Sem colunas existentes, devolve o `CREATE TABLE`.
+pub fn alter_table_sql<'a>(
+    table: &str,
+    source: impl Into<TableSource<'a>>,
+    existing: &DataFrame,
+    options: &DdlOptions,
+) -> EngineResult<SchemaDrift>
+{
+    let source = source.into();
+    if existing.height() == 0
+    {
+        return Ok(SchemaDrift {
+            statements: vec![create_table_sql(table, source, options)?],
+            conflicts: Vec::new(),
+        });
+    }
+
+    let existing = existing_columns(existing)?;
+    let quoted_table = quote_table_name(table)?;
+    let mut drift = SchemaDrift::default();
+
+    for (name, dtype) in source.columns()
+    {
+        let wanted = column_type(source, &name, &dtype, options)?;
+        let stored = stored_name(&name)?;
+        let Some((_, data_type, current)) = existing.iter().find(|(column, ..)| *column == stored)
+        else
+        {
+            drift.statements.push(format!(
+                "ALTER TABLE {} ADD ({} {})",
+                quoted_table,
+                quote_identifier(&name)?,
+                wanted
+            ));
+            continue;
+        };
+
+        match current.as_ref().map(|current| widen(current, &wanted))
+        {
+            Some(Ok(None)) =>
+            {},
+            Some(Ok(Some(column_type))) => drift.statements.push(format!(
+                "ALTER TABLE {} MODIFY ({} {})",
+                quoted_table,
+                quote_identifier(&name)?,
+                column_type
+            )),
+            Some(Err(())) | None => drift
+                .conflicts
+                .push(format!("{}: tabela tem {}, origem pede {}", name, data_type, wanted)),
+        }
+    }
+    Ok(drift)
+}
+
+/// Tipo ampliado para caber `wanted` em `current` (`None` se já cabe), ou `Err` se a troca
+/// exigiria recriar a coluna.
+fn widen(current: &ColumnType, wanted: &ColumnType) -> Result<Option<ColumnType>, ()>
+{
+    use ColumnType::*;
+
+    let widened = match (*current, *wanted)
+    {
+        (
+            Number {
+                precision: p1,
+                scale: s1,
+            },
+            Number {
+                precision: p2,
+                scale: s2,
+            },
+        ) =>
+        {
+            let digits = (p1 as i16 - s1 as i16).max(p2 as i16 - s2 as i16);
+            let scale = s1.max(s2);
+            let precision = (digits + scale as i16).min(38) as u8;
+            (precision > p1 || scale > s1).then_some(Number { precision, scale })
+        },
+        (Varchar2(a), Varchar2(b)) => (b > a).then_some(Varchar2(b)),
+        (Raw(a), Raw(b)) => (b > a).then_some(Raw(b)),
+        (Timestamp(a), Timestamp(b)) => (b > a).then_some(Timestamp(b)),
+        (Date, Timestamp(b)) => Some(Timestamp(b)),
+        // O destino já comporta os valores: números em qualquer tipo numérico ou em texto,
+        // texto em CLOB, binário em BLOB e datas em TIMESTAMP
+        (Number { .. } | BinaryFloat | BinaryDouble, Number { .. } | BinaryFloat | BinaryDouble)
+        | (Varchar2(_) | Clob, Number { .. } | BinaryFloat | BinaryDouble)
+        | (Clob, Varchar2(_) | Clob)
+        | (Blob, Raw(_) | Blob)
+        | (Timestamp(_), Date)
+        | (Date, Date) => None,
+        _ => return Err(()),
+    };
+    Ok(widened)
+}
+
+/// Colunas do DataFrame de `list_columns`: nome, `DATA_TYPE` e o tipo, quando reconhecido.
+fn existing_columns(columns: &DataFrame) -> EngineResult<Vec<(String, String, Option<ColumnType>)>>
+{
+    let names = columns.column("COLUMN_NAME")?.str()?;
+    let types = columns.column("DATA_TYPE")?.str()?;
+    let char_lengths = columns.column("CHAR_LENGTH")?.i64()?;
+    let data_lengths = columns.column("DATA_LENGTH")?.i64()?;
+    let precisions = columns.column("DATA_PRECISION")?.i64()?;
+    let scales = columns.column("DATA_SCALE")?.i64()?;
+
+    let mut existing = Vec::with_capacity(columns.height());
+    for idx in 0..columns.height()
+    {
+        let Some(name) = names.get(idx)
+        else
+        {
+            continue;
+        };
+        let data_type = types.get(idx).unwrap_or_default();
+        let column_type = match data_type
+        {
+            "NUMBER" => Some(match (precisions.get(idx), scales.get(idx))
+            {
+                (Some(precision), scale) => ColumnType::Number {
+                    precision: precision as u8,
+                    scale: scale.unwrap_or(0) as i8,
+                },
+                // NUMBER sem precisão aceita qualquer número
+                (None, _) => ColumnType::Number {
+                    precision: 38,
+                    scale: 127,
+                },
+            }),
+            "FLOAT" | "BINARY_DOUBLE" => Some(ColumnType::BinaryDouble),
+            "BINARY_FLOAT" => Some(ColumnType::BinaryFloat),
+            "VARCHAR2" | "NVARCHAR2" | "CHAR" | "NCHAR" => char_lengths.get(idx).map(|len| ColumnType::Varchar2(len as u32)),
+            "CLOB" | "NCLOB" | "LONG" => Some(ColumnType::Clob),
+            "RAW" => data_lengths.get(idx).map(|len| ColumnType::Raw(len as u32)),
+            "BLOB" | "LONG RAW" => Some(ColumnType::Blob),
+            "DATE" => Some(ColumnType::Date),
+            other if other.starts_with("TIMESTAMP") => Some(ColumnType::Timestamp(
+                other
+                    .split(['(', ')'])
+                    .nth(1)
+                    .and_then(|fsprec| fsprec.parse().ok())
+                    .unwrap_or(6),
+            )),
+            _ => None,
+        };
+        existing.push((name.to_string(), data_type.to_string(), column_type));
+    }
+    Ok(existing)
+}
+
+impl EngineOracle
+{
+    /// Cria a tabela para as colunas da origem e devolve o DDL executado.
+    pub fn create_table<'a>(
+        &self,
+        table: &str,
+        source: impl Into<TableSource<'a>>,
+        options: &DdlOptions,
+    ) -> EngineResult<String>
+    {
+        self.check_write("create_table")?;
+        let sql = create_table_sql(table, source, options)?;
+        self.execute_ddl(&sql)?;
+        Ok(sql)
+    }
+
+    /// Diferenças entre a origem e a tabela no banco, sem executar nada (veja `alter_table_sql`).
+    pub fn schema_drift<'a>(
+        &self,
+        table: &str,
+        source: impl Into<TableSource<'a>>,
+        options: &DdlOptions,
+    ) -> EngineResult<SchemaDrift>
+    {
+        let existing = self.list_columns(table)?;
+        alter_table_sql(table, source, &existing, options)
+    }
+
+    /// Cria a tabela se não existir ou aplica os ALTERs de `schema_drift`. Os conflitos são
+    /// registrados no log e devolvidos, sem instrução executada.
+    ///
+    /// DDL faz commit implícito: instruções já executadas não voltam atrás se uma falhar.
+    pub fn sync_table<'a>(
+        &self,
+        table: &str,
+        source: impl Into<TableSource<'a>>,
+        options: &DdlOptions,
+    ) -> EngineResult<SchemaDrift>
+    {
+        self.check_write("sync_table")?;
+        let drift = self.schema_drift(table, source, options)?;
+        for sql in &drift.statements
+        {
+            self.execute_ddl(sql)?;
+        }
+        for conflict in &drift.conflicts
+        {
+            warn!("Coluna de {} não ajustada: {}", table, conflict);
+        }
+        Ok(drift)
+    }
+
+    fn execute_ddl(&self, sql: &str) -> EngineResult<()>
+    {
+        let conn = self.connection()?;
+        conn.execute(sql, &[]).map_err(|e| EngineError::from(e).with_sql(sql))?;
+        info!("DDL executado: {}", sql.lines().next().unwrap_or_default());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn number(precision: u8, scale: i8) -> ColumnType
+    {
+        ColumnType::Number { precision, scale }
+    }
+
+    #[test]
+    fn create_table_sql_from_data_frame()
+    {
+        let df = df!(
+            "Chassi" => ["ABC", "ÁÉÍ12"],
+            "Qtd" => [1i64, 2],
+            "Grande" => [1u64, 2],
+            "Ativo" => [true, false],
+            "Valor" => [1.5f64, 2.0],
+            "Nome Completo" => [Some("x"), None],
+        )
+        .unwrap();
+        let options = DdlOptions::new()
+            .primary_key(["chassi"])
+            .not_null("Qtd")
+            .tablespace("staging");
+
+        let sql = create_table_sql("sysadm.stg_cripple", &df, &options).unwrap();
+        assert_eq!(
+            sql,
+            "CREATE TABLE SYSADM.STG_CRIPPLE (\n    \
+             CHASSI VARCHAR2(10 CHAR) NOT NULL,\n    \
+             QTD NUMBER(19) NOT NULL,\n    \
+             GRANDE NUMBER(20),\n    \
+             ATIVO NUMBER(1),\n    \
+             VALOR BINARY_DOUBLE,\n    \
+             \"Nome Completo\" VARCHAR2(10 CHAR),\n    \
+             CONSTRAINT PK_STG_CRIPPLE PRIMARY KEY (CHASSI)\n)\n\
+             TABLESPACE STAGING"
+        );
+    }
+
+    #[test]
+    fn create_table_sql_from_schema()
+    {
+        let schema = Schema::from_iter([
+            Field::new("ID".into(), DataType::Int32),
+            Field::new("TEXTO".into(), DataType::String),
+            Field::new("QUANDO".into(), DataType::Datetime(TimeUnit::Nanoseconds, None)),
+            Field::new("DIA".into(), DataType::Date),
+            Field::new("PRECO".into(), DataType::Decimal(Some(12), Some(2))),
+            Field::new("ARQUIVO".into(), DataType::Binary),
+        ]);
+
+        let sql = create_table_sql("T", &schema, &DdlOptions::new()).unwrap();
+        assert_eq!(
+            sql,
+            "CREATE TABLE T (\n    \
+             ID NUMBER(10),\n    \
+             TEXTO VARCHAR2(4000 CHAR),\n    \
+             QUANDO TIMESTAMP(9),\n    \
+             DIA DATE,\n    \
+             PRECO NUMBER(12,2),\n    \
+             ARQUIVO BLOB\n)"
+        );
+
+        let fixed = DdlOptions::new().varchar_sizing(VarcharSizing::Fixed(5000));
+        assert!(create_table_sql("T", &schema, &fixed).unwrap().contains("TEXTO CLOB"));
+    }
+
+    #[test]
+    fn create_table_sql_rejects_bad_input()
+    {
+        let df = df!("ID" => [1i64]).unwrap();
+        let missing_key = create_table_sql("T", &df, &DdlOptions::new().primary_key(["CODIGO"]));
+        assert!(matches!(missing_key, Err(EngineError::InvalidArgument(_))));
+
+        let empty = create_table_sql("T", &DataFrame::empty(), &DdlOptions::new());
+        assert!(matches!(empty, Err(EngineError::InvalidArgument(_))));
+
+        let schema = Schema::from_iter([Field::new("LISTA".into(), DataType::List(Box::new(DataType::Int64)))]);
+        assert!(matches!(
+            create_table_sql("T", &schema, &DdlOptions::new()),
+            Err(EngineError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn widen_only_grows_compatible_types()
+    {
+        assert_eq!(widen(&number(10, 0), &number(12, 2)), Ok(Some(number(12, 2))));
+        assert_eq!(widen(&number(10, 2), &number(10, 0)), Ok(Some(number(12, 2))));
+        assert_eq!(widen(&number(19, 0), &number(10, 0)), Ok(None));
+        assert_eq!(
+            widen(&ColumnType::Varchar2(10), &ColumnType::Varchar2(20)),
+            Ok(Some(ColumnType::Varchar2(20)))
+        );
+        assert_eq!(widen(&ColumnType::Varchar2(20), &ColumnType::Varchar2(10)), Ok(None));
+        assert_eq!(
+            widen(&ColumnType::Date, &ColumnType::Timestamp(6)),
+            Ok(Some(ColumnType::Timestamp(6)))
+        );
+        assert_eq!(widen(&ColumnType::Timestamp(6), &ColumnType::Date), Ok(None));
+        assert_eq!(widen(&ColumnType::Clob, &ColumnType::Varchar2(100)), Ok(None));
+        assert_eq!(widen(&ColumnType::Blob, &ColumnType::Raw(100)), Ok(None));
+        assert_eq!(widen(&ColumnType::BinaryDouble, &number(19, 0)), Ok(None));
+
+        assert_eq!(widen(&ColumnType::Varchar2(100), &ColumnType::Clob), Err(()));
+        assert_eq!(widen(&number(19, 0), &ColumnType::Varchar2(10)), Err(()));
+        assert_eq!(widen(&ColumnType::Raw(10), &ColumnType::Blob), Err(()));
+    }
+
+    #[test]
+    fn reserved_words_are_quoted_in_create_and_alter()
+    {
+        let df = df!(
+            "Chassi" => ["ABC"],
+            "Date" => [NaiveDate::from_ymd_opt(2024, 1, 31).unwrap()],
+            "Level" => [1i32],
+            "Comment" => ["ok"],
+        )
+        .unwrap();
+        let options = DdlOptions::new().primary_key(["Date"]);
+
+        let sql = create_table_sql("stg_excel", &df, &options).unwrap();
+        assert_eq!(
+            sql,
+            "CREATE TABLE STG_EXCEL (\n    \
+             CHASSI VARCHAR2(10 CHAR),\n    \
+             \"DATE\" DATE NOT NULL,\n    \
+             \"LEVEL\" NUMBER(10),\n    \
+             \"COMMENT\" VARCHAR2(10 CHAR),\n    \
+             CONSTRAINT PK_STG_EXCEL PRIMARY KEY (\"DATE\")\n)"
+        );
+
+        // A tabela já tem CHASSI e DATE; LEVEL e COMMENT são novas
+        let existing = df!(
+            "COLUMN_NAME" => ["CHASSI", "DATE"],
+            "DATA_TYPE" => ["VARCHAR2", "DATE"],
+            "CHAR_LENGTH" => [Some(10i64), None],
+            "DATA_LENGTH" => [Some(40i64), Some(7)],
+            "DATA_PRECISION" => [None::<i64>, None],
+            "DATA_SCALE" => [None::<i64>, None],
+        )
+        .unwrap();
+        let drift = alter_table_sql("stg_excel", &df, &existing, &options).unwrap();
+        assert_eq!(
+            drift.statements,
+            vec![
+                "ALTER TABLE STG_EXCEL ADD (\"LEVEL\" NUMBER(10))",
+                "ALTER TABLE STG_EXCEL ADD (\"COMMENT\" VARCHAR2(10 CHAR))",
+            ]
+        );
+        assert!(drift.conflicts.is_empty());
+    }
+}
@@ -0,0 +1,159 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use polars::prelude::DataFrame;
+use tokio::task;
+
+use super::cancel::CancelOnDrop;
+use super::cancel::CancelToken;
+use super::error::EngineResult;
+use super::options::QueryOptions;
+use super::pool::PoolStats;
+
+/// Iterador de DataFrames produzido por `SqlEngine::query_df_stream`.
+pub type DataFrameChunks<'a> = Box<dyn Iterator<Item = EngineResult<DataFrame>> + 'a>;
+
+/// Valor de bind independente do banco, convertido para o tipo nativo de cada backend.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlValue
+{
+    Null,
+    Int(i64),
+    Float(f64),
+    Text(String),
+    Bool(bool),
+    Date(NaiveDate),
+    Datetime(NaiveDateTime),
+}
+
+impl From<i64> for SqlValue
+{
+    fn from(value: i64) -> Self
+    {
+        SqlValue::Int(value)
+    }
+}
+
+impl From<i32> for SqlValue
+{
+    fn from(value: i32) -> Self
+    {
+        SqlValue::Int(value.into())
+    }
+}
+
+impl From<f64> for SqlValue
+{
+    fn from(value: f64) -> Self
+    {
+        SqlValue::Float(value)
+    }
+}
+
+impl From<bool> for SqlValue
+{
+    fn from(value: bool) -> Self
+    {
+        SqlValue::Bool(value)
+    }
+}
+
+impl From<&str> for SqlValue
+{
+    fn from(value: &str) -> Self
+    {
+        SqlValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for SqlValue
+{
+    fn from(value: String) -> Self
+    {
+        SqlValue::Text(value)
+    }
+}
+
+impl From<NaiveDate> for SqlValue
+{
+    fn from(value: NaiveDate) -> Self
+    {
+        SqlValue::Date(value)
+    }
+}
+
+impl From<NaiveDateTime> for SqlValue
+{
+    fn from(value: NaiveDateTime) -> Self
+    {
+        SqlValue::Datetime(value)
+    }
+}
+
+impl<T: Into<SqlValue>> From<Option<T>> for SqlValue
+{
+    fn from(value: Option<T>) -> Self
+    {
+        value.map(Into::into).unwrap_or(SqlValue::Null)
+    }
+}
+
+/// Operações comuns aos bancos suportados, para que handlers e binários não dependam do Oracle.
+///
+/// Os binds são posicionais (`:1`, `:2`, ...), na ordem em que aparecem no SQL.
+pub trait SqlEngine: Send + Sync
+{
+    /// Nome do backend, usado nos logs.
+    fn backend_name(&self) -> &'static str;
+
+    /// Executa a consulta e devolve o resultado inteiro como DataFrame tipado.
+    ///
+    /// Com `cancel`, a leitura é interrompida assim que o token for cancelado.
+    fn query_df(
+        &self,
+        sql: &str,
+        params: &[SqlValue],
+        options: &QueryOptions,
+        cancel: Option<&CancelToken>,
+    ) -> EngineResult<DataFrame>;
+
+    /// Executa uma instrução sem retorno de linhas, com commit, e devolve as linhas afetadas.
+    fn execute(&self, sql: &str, params: &[SqlValue]) -> EngineResult<u64>;
+
+    /// Executa a consulta e devolve DataFrames com até `chunk_rows` linhas cada.
+    fn query_df_stream<'a>(
+        &'a self,
+        sql: &str,
+        params: &[SqlValue],
+        chunk_rows: usize,
+        options: &QueryOptions,
+    ) -> EngineResult<DataFrameChunks<'a>>;
+
+    /// Confirma que o banco responde, reconectando se preciso, e devolve a latência do round-trip.
+    fn ping(&self) -> EngineResult<Duration>;
+
+    /// Estatísticas do pool de conexões, quando o backend usa um.
+    fn pool_stats(&self) -> EngineResult<Option<PoolStats>>
+    {
+        Ok(None)
+    }
+}
+
+/// Executa `SqlEngine::query_df` em uma thread de bloqueio, sem ocupar os workers do Tokio.
+///
+/// Se o future for descartado antes de terminar, a leitura é cancelada.
+pub async fn query_df_async(
+    engine: Arc<dyn SqlEngine>,
+    sql: impl Into<String>,
+    params: Vec<SqlValue>,
+    options: QueryOptions,
+) -> EngineResult<DataFrame>
+{
+    let sql = sql.into();
+    let cancel = CancelToken::new();
+    let _guard = CancelOnDrop(cancel.clone());
+
+    task::spawn_blocking(move || engine.query_df(&sql, &params, &options, Some(&cancel))).await?
+}
@@ -0,0 +1,312 @@
+use std::path::PathBuf;
+
+use polars::prelude::PolarsError;
+use thiserror::Error;
+use tokio::task::JoinError;
+
+use super::config::ConfigError;
+
+/// Código DPI de estouro do call timeout da conexão.
+const DPI_CALL_TIMEOUT: i32 = 1067;
+/// ORA-01013: operação cancelada pelo usuário (`break_execution`).
+const ORA_USER_CANCEL: i32 = 1013;
+/// ORA-24418 / ORA-24457: nenhuma sessão livre no pool (sem espera ou após o tempo de espera).
+const ORA_POOL_EXHAUSTED: [i32; 2] = [24418, 24457];
+/// Códigos ORA de conexão perdida ou inalcançável: fim de arquivo no canal (03113), não
+/// conectado (03114, 01012), timeout de rede (03135, 12170), sessão encerrada (00028, 02396),
+/// listener indisponível (12541, 12514) e falhas de transporte (12537, 12547, 12560, 12571).
+const ORA_CONNECTION_LOST: [i32; 13] = [
+    28, 1012, 2396, 3113, 3114, 3135, 12170, 12514, 12537, 12541, 12547, 12560, 12571,
+];
+/// DPI-1010 (não conectado) e DPI-1080 (conexão fechada por um erro ORA).
+const DPI_CONNECTION_LOST: [i32; 2] = [1010, 1080];
+
+pub type EngineResult<T> = Result<T, EngineError>;
+
+/// Erros do `EngineOracle`.
+///
+/// Variantes ligadas a uma instrução carregam o `sql_id` (o mesmo de `V$SQL`), para localizar
+/// a consulta no servidor, e as ligadas ao Oracle carregam o código ORA quando existe.
+#[derive(Debug, Error)]
+pub enum EngineError
+{
+    #[error("configuração inválida: {0}")]
+    Config(#[from] ConfigError),
+
+    #[error("falha ao conectar no Oracle{}: {source}", ora_suffix(*code))]
+    Connect
+    {
+        code: Option<i32>, source: Box<oracle::Error>
+    },
+
+    #[error("pool de conexões esgotado{}: {source}", ora_suffix(*code))]
+    PoolExhausted
+    {
+        code: Option<i32>, source: Box<oracle::Error>
+    },
+
+    #[error("erro no Oracle{}{}: {source}", ora_suffix(*code), sql_suffix(sql_id))]
+    Oracle
+    {
+        code: Option<i32>,
+        sql_id: Option<String>,
+        source: Box<oracle::Error>,
+    },
+
+    #[error("falha ao converter coluna {column} (linha {row}){}: {message}", sql_suffix(sql_id))]
+    Conversion
+    {
+        column: String,
+        row: usize,
+        message: String,
+        sql_id: Option<String>,
+    },
+
+    #[error("erro no SQLite: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("argumento inválido: {0}")]
+    InvalidArgument(String),
+
+    #[error("SQL recusado: {0}")]
+    SqlRejected(String),
+
+    #[error("custo estimado {cost} acima do limite {limit}{}", sql_suffix(sql_id))]
+    CostExceeded
+    {
+        cost: u64, limit: u64, sql_id: Option<String>
+    },
+
+    #[error("falha ao montar DataFrame: {0}")]
+    Polars(#[from] PolarsError),
+
+    /// `source` é o erro do driver, quando o prazo estourou no Oracle.
+    #[error("tempo esgotado{}{}", ora_suffix(*code), sql_suffix(sql_id))]
+    Timeout
+    {
+        code: Option<i32>,
+        sql_id: Option<String>,
+        source: Option<Box<oracle::Error>>,
+    },
+
+    /// `source` é o erro do driver (ORA-01013), quando a instrução foi interrompida no servidor.
+    #[error("consulta cancelada{}", sql_suffix(sql_id))]
+    Cancelled
+    {
+        sql_id: Option<String>,
+        source: Option<Box<oracle::Error>>,
+    },
+
+    #[error("falha na tarefa de fetch: {0}")]
+    Task(#[from] JoinError),
+
+    #[error("falha de E/S em {}: {source}", path.display())]
+    Io
+    {
+        path: PathBuf, source: std::io::Error
+    },
+
+    #[error("consulta não registrada: {0}")]
+    UnknownQuery(String),
+
+    #[error("consulta inválida em {}:{line}: {reason}", path.display())]
+    QueryFile
+    {
+        path: PathBuf, line: usize, reason: String
+    },
+
+    #[error("arquivo de estado inválido em {}: {source}", path.display())]
+    State
+    {
+        path: PathBuf, source: serde_json::Error
+    },
+
+    #[error("falha ao mapear o campo {field}: {message}")]
+    Mapping
+    {
+        field: String, message: String
+    },
+}
+
+impl EngineError
+{
+    /// Classifica um erro do driver ocorrido ao abrir conexões.
+    pub fn connect(source: oracle::Error) -> Self
+    {
+        let code = source.oci_code();
+        Self::Connect {
+            code,
+            source: Box::new(source),
+        }
+    }
+
+    /// Erro de E/S associado ao arquivo que o causou.
+    pub fn io(path: impl Into<PathBuf>, source: std::io::Error) -> Self
+    {
+        Self::Io {
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// Associa o erro à instrução que o originou, preenchendo o `sql_id` quando ainda vazio.
+    pub fn with_sql(mut self, sql: &str) -> Self
+    {
+        match &mut self
+        {
+            EngineError::Oracle { sql_id, .. }
+            | EngineError::Conversion { sql_id, .. }
+            | EngineError::Timeout { sql_id, .. }
+            | EngineError::CostExceeded { sql_id, .. }
+            | EngineError::Cancelled { sql_id, .. } =>
+            {
+                sql_id.get_or_insert_with(|| self::sql_id(sql));
+            },
+            _ =>
+            {},
+        }
+        self
+    }
+
+    /// Código ORA associado ao erro, quando houver.
+    pub fn ora_code(&self) -> Option<i32>
+    {
+        match self
+        {
+            EngineError::Connect { code, .. }
+            | EngineError::PoolExhausted { code, .. }
+            | EngineError::Oracle { code, .. }
+            | EngineError::Timeout { code, .. } => *code,
+            _ => None,
+        }
+    }
+
+    /// Indica se o erro veio da perda da conexão com o servidor, caso em que reconectar e
+    /// repetir uma leitura é seguro.
+    pub fn is_connection_lost(&self) -> bool
+    {
+        match self
+        {
+            EngineError::Connect { code, source } | EngineError::Oracle { code, source, .. } =>
+            {
+                code.is_some_and(|c| ORA_CONNECTION_LOST.contains(&c))
+                    || source.dpi_code().is_some_and(|c| DPI_CONNECTION_LOST.contains(&c))
+            },
+            _ => false,
+        }
+    }
+
+    /// `sql_id` da instrução associada ao erro, quando houver.
+    pub fn sql_id(&self) -> Option<&str>
+    {
+        match self
+        {
+            EngineError::Oracle { sql_id, .. }
+            | EngineError::Conversion { sql_id, .. }
+            | EngineError::Timeout { sql_id, .. }
+            | EngineError::CostExceeded { sql_id, .. }
+            | EngineError::Cancelled { sql_id, .. } => sql_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Status HTTP adequado para o erro, independente do framework web.
+    ///
+    /// Erros do chamador (argumento inválido, SQL recusado, custo acima do limite) dão 400,
+    /// consulta não registrada 404, banco inalcançável ou pool esgotado 503, erro do Oracle 502
+    /// e tempo esgotado 504; o resto é 500.
+    pub fn http_status(&self) -> u16
+    {
+        match self
+        {
+            EngineError::InvalidArgument(_) | EngineError::SqlRejected(_) | EngineError::CostExceeded { .. } => 400,
+            EngineError::UnknownQuery(_) => 404,
+            EngineError::Connect { .. } | EngineError::PoolExhausted { .. } => 503,
+            EngineError::Oracle { .. } => 502,
+            EngineError::Timeout { .. } => 504,
+            // cancelamento só ocorre quando o cliente já desconectou; o status não chega a ser lido
+            EngineError::Cancelled { .. } => 500,
+            EngineError::Config(_)
+            | EngineError::Conversion { .. }
+            | EngineError::Polars(_)
+            | EngineError::Sqlite(_)
+            | EngineError::Task(_)
+            | EngineError::Io { .. }
+            | EngineError::QueryFile { .. }
+            | EngineError::Mapping { .. }
+            | EngineError::State { .. } => 500,
+        }
+    }
+}
+
+impl From<oracle::Error> for EngineError
+{
+    fn from(source: oracle::Error) -> Self
+    {
+        let code = source.oci_code();
+        if source.dpi_code() == Some(DPI_CALL_TIMEOUT)
+        {
+            EngineError::Timeout {
+                code,
+                sql_id: None,
+                source: Some(Box::new(source)),
+            }
+        }
+        else if code == Some(ORA_USER_CANCEL)
+        {
+            EngineError::Cancelled {
+                sql_id: None,
+                source: Some(Box::new(source)),
+            }
+        }
+        else if code.is_some_and(|c| ORA_POOL_EXHAUSTED.contains(&c))
+        {
+            EngineError::PoolExhausted {
+                code,
+                source: Box::new(source),
+            }
+        }
+        else
+        {
+            EngineError::Oracle {
+                code,
+                sql_id: None,
+                source: Box::new(source),
+            }
+        }
+    }
+}
+
+/// Calcula o SQL_ID que o Oracle atribui ao texto da instrução (o mesmo exibido em `V$SQL`).
+///
+/// É a base 32 dos últimos 64 bits do MD5 do texto terminado em `\0`.
+pub fn sql_id(sql: &str) -> String
+{
+    const ALPHABET: &[u8; 32] = b"0123456789abcdfghjkmnpqrstuvwxyz";
+
+    let mut bytes = sql.as_bytes().to_vec();
+    bytes.push(0);
+    let digest = md5::compute(&bytes).0;
+
+    let msb = u32::from_le_bytes([digest[8], digest[9], digest[10], digest[11]]) as u64;
+    let lsb = u32::from_le_bytes([digest[12], digest[13], digest[14], digest[15]]) as u64;
+    let mut value = (msb << 32) | lsb;
+
+    let mut id = [0u8; 13];
+    for slot in id.iter_mut().rev()
+    {
+        *slot = ALPHABET[(value % 32) as usize];
+        value /= 32;
+    }
+    String::from_utf8_lossy(&id).into_owned()
+}
+
+fn ora_suffix(code: Option<i32>) -> String
+{
+    code.map(|c| format!(" (ORA-{:05})", c)).unwrap_or_default()
+}
+
+fn sql_suffix(sql_id: &Option<String>) -> String
+{
+    sql_id.as_ref().map(|id| format!(" [sql_id {}]", id)).unwrap_or_default()
+}
@@ -0,0 +1,429 @@
+use std::fs;
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::Write;
+use std::iter;
+use std::path::Path;
+
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use log::info;
+use polars::io::csv::write::BatchedWriter as CsvBatchedWriter;
+use polars::io::ipc::BatchedWriter as IpcBatchedWriter;
+use polars::io::json::BatchedWriter as NdjsonBatchedWriter;
+use polars::io::parquet::write::BatchedWriter as ParquetBatchedWriter;
+use polars::prelude::*;
+
+use super::cancel::CancelToken;
+use super::engine::SqlEngine;
+use super::engine::SqlValue;
+use super::error::EngineError;
+use super::error::EngineResult;
+use super::options::QueryOptions;
+
+/// Formato do arquivo gerado por `export_query`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportFormat
+{
+    Parquet
+    {
+        compression: ParquetCompression
+    },
+    /// Arrow IPC (Feather v2).
+    Ipc,
+    Csv
+    {
+        separator: u8, quote: u8, header: bool
+    },
+    /// Um objeto JSON por linha.
+    Ndjson,
+}
+
+impl ExportFormat
+{
+    /// Deduz o formato pela extensão do arquivo (`.parquet`, `.arrow`/`.ipc`/`.feather`,
+    /// `.csv`, `.ndjson`/`.jsonl`), com as opções padrão de cada um.
+    pub fn from_path(path: &Path) -> Option<Self>
+    {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str()
+        {
+            "parquet" => Some(ExportFormat::Parquet {
+                compression: ParquetCompression::default(),
+            }),
+            "arrow" | "ipc" | "feather" => Some(ExportFormat::Ipc),
+            "csv" => Some(ExportFormat::Csv {
+                separator: b',',
+                quote: b'"',
+                header: true,
+            }),
+            "ndjson" | "jsonl" => Some(ExportFormat::Ndjson),
+            _ => None,
+        }
+    }
+}
+
+/// Opções de `export_query`.
+#[derive(Debug, Clone)]
+pub struct ExportOptions
+{
+    pub format: ExportFormat,
+    /// Linhas por bloco lido do banco e gravado no arquivo.
+    pub chunk_rows: usize,
+    pub query: QueryOptions,
+    /// Interrompe a exportação, conferido a cada bloco; o arquivo temporário é removido.
+    pub cancel: Option<CancelToken>,
+}
+
+impl ExportOptions
+{
+    pub fn new(format: ExportFormat) -> Self
+    {
+        Self {
+            format,
+            chunk_rows: 100_000,
+            query: QueryOptions::default(),
+            cancel: None,
+        }
+    }
+}
+
+/// Writer em lotes de cada formato, criado a partir do schema do primeiro bloco.
+enum ChunkWriter<W: Write>
+{
+    // Em caixa: o writer Parquet é bem maior que os outros
+    Parquet(Box<ParquetBatchedWriter<W>>),
+    Ipc(IpcBatchedWriter<W>),
+    Csv(CsvBatchedWriter<W>),
+    Ndjson(NdjsonBatchedWriter<W>),
+}
+
+impl<W: Write + Send> ChunkWriter<W>
+{
+    fn new(format: &ExportFormat, out: W, schema: &Schema) -> PolarsResult<Self>
+    {
+        let writer = match format
+        {
+            ExportFormat::Parquet { compression } => ChunkWriter::Parquet(Box::new(
+                ParquetWriter::new(out).with_compression(*compression).batched(schema)?,
+            )),
+            ExportFormat::Ipc => ChunkWriter::Ipc(IpcWriter::new(out).batched(schema)?),
+            ExportFormat::Csv {
+                separator,
+                quote,
+                header,
+            } => ChunkWriter::Csv(
+                CsvWriter::new(out)
+                    .with_separator(*separator)
+                    .with_quote_char(*quote)
+                    .include_header(*header)
+                    .batched(schema)?,
+            ),
+            ExportFormat::Ndjson => ChunkWriter::Ndjson(NdjsonBatchedWriter::new(out)),
+        };
+        Ok(writer)
+    }
+
+    fn write(&mut self, df: &DataFrame) -> PolarsResult<()>
+    {
+        match self
+        {
+            ChunkWriter::Parquet(writer) => writer.write_batch(df),
+            ChunkWriter::Ipc(writer) => writer.write_batch(df),
+            ChunkWriter::Csv(writer) => writer.write_batch(df),
+            ChunkWriter::Ndjson(writer) => writer.write_batch(df),
+        }
+    }
+
+    fn finish(self) -> PolarsResult<()>
+    {
+        match self
+        {
+            ChunkWriter::Parquet(writer) => writer.finish().map(|_| ()),
+            ChunkWriter::Ipc(mut writer) => writer.finish(),
+            ChunkWriter::Csv(mut writer) => writer.finish(),
+            ChunkWriter::Ndjson(_) => Ok(()),
+        }
+    }
+}
+
+/// Executa a consulta e grava o resultado em `path`, bloco a bloco, sem montar o resultado inteiro.
+///
+/// O arquivo é escrito como `<path>.tmp`, gravado em disco (`sync_all`) e só então renomeado,
+/// então uma exportação que falha ou é cancelada (`ExportOptions::cancel`) não deixa um arquivo
+/// truncado no destino. `on_progress` recebe o total de linhas gravadas após cada bloco.
+/// Retorna o número de linhas exportadas.
+pub fn export_query(
+    engine: &dyn SqlEngine,
+    sql: &str,
+    params: &[SqlValue],
+    path: &Path,
+    options: &ExportOptions,
+    mut on_progress: impl FnMut(u64),
+) -> EngineResult<u64>
+{
+    let tmp_path = path.with_extension(format!(
+        "{}.tmp",
+        path.extension().and_then(|e| e.to_str()).unwrap_or_default()
+    ));
+
+    let result = (|| -> EngineResult<u64> {
+        let mut chunks = engine.query_df_stream(sql, params, options.chunk_rows, &options.query)?;
+        // O stream sempre produz ao menos um bloco (vazio, se não houver linhas), que traz o
+        // schema das colunas para criar o writer
+        let first = chunks.next().transpose()?.unwrap_or_default();
+
+        let file = File::create(&tmp_path).map_err(|e| EngineError::io(&tmp_path, e))?;
+        let mut out = BufWriter::new(file);
+        let mut writer = ChunkWriter::new(&options.format, &mut out, first.schema())?;
+        let mut rows = 0u64;
+        for chunk in iter::once(Ok(first)).chain(chunks)
+        {
+            if options.cancel.as_ref().is_some_and(CancelToken::is_cancelled)
+            {
+                return Err(EngineError::Cancelled {
+                    sql_id: None,
+                    source: None,
+                }
+                .with_sql(sql));
+            }
+            let chunk = chunk?;
+            writer.write(&chunk)?;
+
+            rows += chunk.height() as u64;
+            on_progress(rows);
+        }
+        writer.finish()?;
+
+        // O drop do BufWriter engoliria um erro de escrita: descarrega e sincroniza aqui
+        let file = out.into_inner().map_err(|e| EngineError::io(&tmp_path, e.into_error()))?;
+        file.sync_all().map_err(|e| EngineError::io(&tmp_path, e))?;
+        Ok(rows)
+    })();
+
+    match result
+    {
+        Ok(rows) =>
+        {
+            fs::rename(&tmp_path, path).map_err(|e| EngineError::io(path, e))?;
+            info!("{} linhas exportadas para {}.", rows, path.display());
+            Ok(rows)
+        },
+        Err(e) =>
+        {
+            let _ = fs::remove_file(&tmp_path);
+            Err(e)
+        },
+    }
+}
+
+/// Converte um parâmetro de linha de comando em bind.
+///
+/// Aceita prefixo de tipo (`int:`, `float:`, `text:`, `date:`, `datetime:`, `bool:`) ou `null`;
+/// sem prefixo, números viram `Int`/`Float` e o resto `Text`.
+pub fn parse_param(text: &str) -> Result<SqlValue, String>
+{
+    if text.eq_ignore_ascii_case("null")
+    {
+        return Ok(SqlValue::Null);
+    }
+
+    let Some((kind, value)) = text.split_once(':')
+    else
+    {
+        return Ok(text
+            .parse::<i64>()
+            .map(SqlValue::Int)
+            .or_else(|_| text.parse::<f64>().map(SqlValue::Float))
+            .unwrap_or_else(|_| SqlValue::Text(text.to_string())));
+    };
+
+    let invalid = |e: &dyn std::fmt::Display| format!("parâmetro {:?} inválido: {}", text, e);
+    match kind
+    {
+        "int" => value.parse().map(SqlValue::Int).map_err(|e| invalid(&e)),
+        "float" => value.parse().map(SqlValue::Float).map_err(|e| invalid(&e)),
+        "text" => Ok(SqlValue::Text(value.to_string())),
+        "bool" => value.parse().map(SqlValue::Bool).map_err(|e| invalid(&e)),
+        "date" => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map(SqlValue::Date)
+            .map_err(|e| invalid(&e)),
+        "datetime" => NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+            .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S"))
+            .map(SqlValue::Datetime)
+            .map_err(|e| invalid(&e)),
+        // Sem prefixo conhecido, o `:` faz parte do texto (ex.: um horário)
+        _ => Ok(SqlValue::Text(text.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::oracle::testing::sqlite_engine;
+    use crate::oracle::testing::TestDir;
+
+    fn options(path: &Path, chunk_rows: usize) -> ExportOptions
+    {
+        ExportOptions {
+            chunk_rows,
+            ..ExportOptions::new(ExportFormat::from_path(path).unwrap())
+        }
+    }
+
+    #[test]
+    fn parse_param_infers_numbers_and_text()
+    {
+        assert_eq!(parse_param("42"), Ok(SqlValue::Int(42)));
+        assert_eq!(parse_param("-1.5"), Ok(SqlValue::Float(-1.5)));
+        assert_eq!(parse_param("BR01"), Ok(SqlValue::Text("BR01".to_string())));
+        assert_eq!(parse_param("NULL"), Ok(SqlValue::Null));
+        // Sem prefixo conhecido, o `:` faz parte do texto
+        assert_eq!(parse_param("10:30"), Ok(SqlValue::Text("10:30".to_string())));
+    }
+
+    #[test]
+    fn parse_param_honours_type_prefixes()
+    {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        assert_eq!(parse_param("int:7"), Ok(SqlValue::Int(7)));
+        assert_eq!(parse_param("float:7"), Ok(SqlValue::Float(7.0)));
+        assert_eq!(parse_param("text:007"), Ok(SqlValue::Text("007".to_string())));
+        assert_eq!(parse_param("text:"), Ok(SqlValue::Text(String::new())));
+        assert_eq!(parse_param("bool:true"), Ok(SqlValue::Bool(true)));
+        assert_eq!(parse_param("date:2024-03-01"), Ok(SqlValue::Date(date)));
+        let datetime = SqlValue::Datetime(date.and_hms_opt(8, 15, 0).unwrap());
+        assert_eq!(parse_param("datetime:2024-03-01 08:15:00"), Ok(datetime.clone()));
+        assert_eq!(parse_param("datetime:2024-03-01T08:15:00"), Ok(datetime));
+
+        for text in ["int:x", "float:", "bool:sim", "date:01/03/2024", "datetime:2024-03-01"]
+        {
+            assert!(parse_param(text).is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn format_from_extension()
+    {
+        assert_eq!(
+            ExportFormat::from_path(Path::new("saida.PARQUET")),
+            Some(ExportFormat::Parquet {
+                compression: ParquetCompression::default()
+            })
+        );
+        assert_eq!(ExportFormat::from_path(Path::new("saida.feather")), Some(ExportFormat::Ipc));
+        assert_eq!(ExportFormat::from_path(Path::new("saida.jsonl")), Some(ExportFormat::Ndjson));
+        assert_eq!(ExportFormat::from_path(Path::new("saida.xlsx")), None);
+        assert_eq!(ExportFormat::from_path(Path::new("saida")), None);
+    }
+
+    #[test]
+    fn export_query_writes_chunks_and_reports_progress()
+    {
+        let dir = TestDir::new();
+        let path = dir.path().join("chassi.csv");
+        let mut progress = Vec::new();
+
+        let rows = export_query(
+            &sqlite_engine(),
+            "SELECT id, nome FROM chassi WHERE id >= ? ORDER BY id",
+            &[SqlValue::Int(2)],
+            &path,
+            &options(&path, 2),
+            |rows| progress.push(rows),
+        )
+        .unwrap();
+
+        assert_eq!(rows, 4);
+        assert_eq!(progress, vec![2, 4]);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "id,nome\n2,DEF\n3,\n4,GHI\n5,JKL\n");
+        assert_eq!(dir.file_names(), vec!["chassi.csv"]);
+    }
+
+    #[test]
+    fn export_query_round_trips_parquet_and_empty_results()
+    {
+        let dir = TestDir::new();
+        let engine = sqlite_engine();
+        let path = dir.path().join("chassi.parquet");
+        let sql = "SELECT id, nome, preco, alterado FROM chassi ORDER BY id";
+
+        assert_eq!(export_query(&engine, sql, &[], &path, &options(&path, 2), |_| {}).unwrap(), 5);
+        let written = ParquetReader::new(File::open(&path).unwrap()).finish().unwrap();
+        let expected = engine.query_df(sql, &[], &QueryOptions::default(), None).unwrap();
+        assert!(written.equals_missing(&expected));
+
+        // Sem linhas, o arquivo ainda é gerado, com o cabeçalho
+        let empty = dir.path().join("vazio.csv");
+        let rows = export_query(
+            &engine,
+            "SELECT id, nome FROM chassi WHERE 1 = 0",
+            &[],
+            &empty,
+            &options(&empty, 2),
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(rows, 0);
+        assert_eq!(fs::read_to_string(&empty).unwrap(), "id,nome\n");
+
+        let ndjson = dir.path().join("chassi.ndjson");
+        export_query(
+            &engine,
+            "SELECT id, nome FROM chassi WHERE id = 3",
+            &[],
+            &ndjson,
+            &options(&ndjson, 2),
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(fs::read_to_string(&ndjson).unwrap(), "{\"id\":3,\"nome\":null}\n");
+    }
+
+    #[test]
+    fn export_query_keeps_the_destination_on_failure()
+    {
+        let dir = TestDir::new();
+        let path = dir.path().join("chassi.csv");
+        fs::write(&path, "anterior").unwrap();
+
+        let result = export_query(
+            &sqlite_engine(),
+            "SELECT coluna_inexistente FROM chassi",
+            &[],
+            &path,
+            &options(&path, 2),
+            |_| {},
+        );
+        assert!(result.is_err());
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "anterior");
+        assert_eq!(dir.file_names(), vec!["chassi.csv"]);
+    }
+
+    #[test]
+    fn export_query_stops_when_cancelled()
+    {
+        let dir = TestDir::new();
+        let path = dir.path().join("chassi.csv");
+        fs::write(&path, "anterior").unwrap();
+
+        // Cancelada depois do primeiro bloco, com o temporário já criado
+        let cancel = CancelToken::new();
+        let export = ExportOptions {
+            cancel: Some(cancel.clone()),
+            ..options(&path, 2)
+        };
+        let mut progress = Vec::new();
+        let result = export_query(&sqlite_engine(), "SELECT id FROM chassi", &[], &path, &export, |rows| {
+            progress.push(rows);
+            cancel.cancel();
+        });
+
+        assert!(matches!(result, Err(EngineError::Cancelled { .. })), "{:?}", result);
+        assert_eq!(progress, vec![2]);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "anterior");
+        assert_eq!(dir.file_names(), vec!["chassi.csv"]);
+    }
+}
@@ -0,0 +1,556 @@
+use oracle::Connection;
+
+use super::error::EngineError;
+use super::error::EngineResult;
+use super::sql::stored_name;
+
+/// Palavras que não aparecem em uma leitura: DML, DDL, controle de transação, PL/SQL
+/// (inclusive `WITH FUNCTION`) e `NEXTVAL`, que avança sequences.
+const DENIED_WORDS: [&str; 20] = [
+    "INSERT",
+    "UPDATE",
+    "DELETE",
+    "MERGE",
+    "UPSERT",
+    "CREATE",
+    "ALTER",
+    "DROP",
+    "TRUNCATE",
+    "RENAME",
+    "GRANT",
+    "REVOKE",
+    "COMMIT",
+    "ROLLBACK",
+    "SAVEPOINT",
+    "LOCK",
+    "BEGIN",
+    "DECLARE",
+    "PROCEDURE",
+    "NEXTVAL",
+];
+
+/// Palavras que encerram a lista de tabelas de um `FROM`.
+const FROM_END_WORDS: [&str; 15] = [
+    "WHERE",
+    "GROUP",
+    "ORDER",
+    "HAVING",
+    "CONNECT",
+    "START",
+    "UNION",
+    "INTERSECT",
+    "MINUS",
+    "EXCEPT",
+    "FETCH",
+    "OFFSET",
+    "FOR",
+    "MODEL",
+    "WINDOW",
+];
+
+/// Modo somente leitura para SQL vindo de fora (parâmetros de API, consultas registradas).
+///
+/// Aceita uma única instrução `SELECT` ou `WITH`, sem DML, DDL, PL/SQL nem database links,
+/// e só permite qualificar tabelas com os schemas de `allowed_schemas`; nomes sem schema
+/// resolvem para o `CURRENT_SCHEMA` da sessão. Com o guard ativo, o engine também recusa
+/// `execute`, `write_polars_df`, transações e chamadas de procedure.
+///
+/// A análise é léxica: funções de pacote (`PKG.FUNCAO(...)`) não são verificadas. As
+/// permissões do usuário no banco e `read_only_session` continuam sendo a barreira final.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SqlGuard
+{
+    /// Schemas aceitos como qualificador (`SYSADM.TABELA`), como ficam no dicionário.
+    pub allowed_schemas: Vec<String>,
+    /// Executa cada leitura em `SET TRANSACTION READ ONLY` (exige pool de conexões).
+    pub read_only_session: bool,
+}
+
+impl SqlGuard
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    /// Permite `schema.tabela` para o schema informado (nomes simples vão para maiúsculas).
+    pub fn allow_schema(mut self, schema: impl AsRef<str>) -> EngineResult<Self>
+    {
+        self.allowed_schemas.push(stored_name(schema.as_ref())?);
+        Ok(self)
+    }
+
+    pub fn read_only_session(mut self, read_only_session: bool) -> Self
+    {
+        self.read_only_session = read_only_session;
+        self
+    }
+
+    /// Confere a instrução, devolvendo `EngineError::SqlRejected` com o motivo da recusa.
+    pub fn check(&self, sql: &str) -> EngineResult<()>
+    {
+        let mut tokens = tokenize(sql)?;
+        while tokens.last() == Some(&Token::Symbol(';'))
+        {
+            tokens.pop();
+        }
+        if tokens.contains(&Token::Symbol(';'))
+        {
+            return Err(rejected("mais de uma instrução"));
+        }
+
+        let first = tokens.iter().find(|t| **t != Token::Symbol('('));
+        match first
+        {
+            None => return Err(rejected("instrução vazia")),
+            Some(token) if token.is_word("SELECT") || token.is_word("WITH") =>
+            {},
+            Some(token) => return Err(rejected(&format!("apenas SELECT ou WITH são aceitos, não {}", token))),
+        }
+
+        if let Some(word) = tokens.iter().find(|t| DENIED_WORDS.iter().any(|w| t.is_word(w)))
+        {
+            return Err(rejected(&format!("{} não é permitido em uma leitura", word)));
+        }
+        if tokens.contains(&Token::Symbol('@'))
+        {
+            return Err(rejected("database links não são permitidos"));
+        }
+
+        self.check_schemas(&tokens)
+    }
+
+    /// Confere o schema das tabelas do `FROM`/`JOIN` e de qualquer nome com três partes.
+    fn check_schemas(&self, tokens: &[Token]) -> EngineResult<()>
+    {
+        // Um nível por parêntese aberto. O FROM de `EXTRACT(... FROM col)` e `TRIM(... FROM col)`
+        // não abre lista de tabelas: só conta no nível que começou com SELECT.
+        let mut frames = vec![Frame::default()];
+        let mut expect_table = false;
+        let mut i = 0;
+        while i < tokens.len()
+        {
+            let token = &tokens[i];
+            let frame = frames.last_mut().ok_or_else(|| rejected("parênteses desbalanceados"))?;
+            match token
+            {
+                Token::Symbol('(') =>
+                {
+                    frames.push(Frame::default());
+                    expect_table = false;
+                },
+                Token::Symbol(')') =>
+                {
+                    frames.pop();
+                },
+                Token::Symbol(',') if frame.in_from => expect_table = true,
+                Token::Word(word) if word == "SELECT" => frame.query = true,
+                Token::Word(word) if word == "FROM" && frame.query =>
+                {
+                    frame.in_from = true;
+                    expect_table = true;
+                },
+                Token::Word(word) if word == "JOIN" => expect_table = true,
+                Token::Word(word) if FROM_END_WORDS.contains(&word.as_str()) => frame.in_from = false,
+                Token::Word(_) | Token::Quoted(_) =>
+                {
+                    let parts = qualified_name(&tokens[i..]);
+                    let is_table = expect_table && !token.is_word("LATERAL") && !token.is_word("ONLY");
+                    if (is_table && parts.len() > 1) || parts.len() > 2
+                    {
+                        let schema = parts[0];
+                        if !self.allowed_schemas.iter().any(|s| s == schema)
+                        {
+                            return Err(rejected(&format!("schema {} fora da lista permitida", schema)));
+                        }
+                    }
+                    if is_table
+                    {
+                        expect_table = false;
+                    }
+                    // Pula o nome inteiro e os pontos entre as partes
+                    i += parts.len() * 2 - 1;
+                    continue;
+                },
+                _ =>
+                {},
+            }
+            i += 1;
+        }
+        if frames.len() != 1
+        {
+            return Err(rejected("parênteses desbalanceados"));
+        }
+        Ok(())
+    }
+
+    /// Abre a transação somente leitura na conexão, quando configurado.
+    ///
+    /// A transação termina com `end_read_only` ou com o rollback que o pool faz ao receber a
+    /// conexão de volta.
+    pub(crate) fn begin_read_only(&self, conn: &Connection) -> EngineResult<()>
+    {
+        if self.read_only_session
+        {
+            conn.execute("SET TRANSACTION READ ONLY", &[])?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn end_read_only(&self, conn: &Connection) -> EngineResult<()>
+    {
+        if self.read_only_session
+        {
+            conn.rollback()?;
+        }
+        Ok(())
+    }
+}
+
+/// Erro devolvido às operações de escrita quando o engine está em modo somente leitura.
+pub(crate) fn read_only_error(operation: &str) -> EngineError
+{
+    rejected(&format!("{} não é permitido com o engine em modo somente leitura", operation))
+}
+
+fn rejected(reason: &str) -> EngineError
+{
+    EngineError::SqlRejected(reason.to_string())
+}
+
+/// Nível de parênteses em `SqlGuard::check_schemas`.
+#[derive(Debug, Default)]
+struct Frame
+{
+    /// O nível é uma consulta (começou com SELECT).
+    query: bool,
+    /// Está na lista de tabelas do FROM.
+    in_from: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token
+{
+    /// Palavra ou identificador sem aspas, em maiúsculas.
+    Word(String),
+    /// Identificador entre aspas, como escrito.
+    Quoted(String),
+    /// Literal de texto ou número.
+    Literal,
+    /// Bind (`:1`, `:nome`), sem os dois-pontos e como escrito.
+    Bind(String),
+    Symbol(char),
+}
+
+impl Token
+{
+    fn is_word(&self, word: &str) -> bool
+    {
+        matches!(self, Token::Word(w) if w == word)
+    }
+
+    fn name(&self) -> Option<&str>
+    {
+        match self
+        {
+            Token::Word(name) | Token::Quoted(name) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Token
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            Token::Word(word) => write!(f, "{}", word),
+            Token::Quoted(name) => write!(f, "\"{}\"", name),
+            Token::Literal => write!(f, "literal"),
+            Token::Bind(name) => write!(f, ":{}", name),
+            Token::Symbol(c) => write!(f, "{:?}", c),
+        }
+    }
+}
+
+/// Partes de `a.b.c` a partir do início de `tokens`.
+fn qualified_name(tokens: &[Token]) -> Vec<&str>
+{
+    let mut parts = Vec::new();
+    let mut rest = tokens;
+    while let Some(name) = rest.first().and_then(Token::name)
+    {
+        parts.push(name);
+        match rest.get(1)
+        {
+            Some(Token::Symbol('.')) if rest.get(2).and_then(Token::name).is_some() => rest = &rest[2..],
+            _ => break,
+        }
+    }
+    parts
+}
+
+/// Quebra o SQL em tokens, descartando comentários (e hints) e o conteúdo dos literais.
+fn tokenize(sql: &str) -> EngineResult<Vec<Token>>
+{
+    let chars: Vec<char> = sql.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len()
+    {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if c.is_whitespace()
+        {
+            i += 1;
+        }
+        else if c == '-' && next == Some('-')
+        {
+            while i < chars.len() && chars[i] != '\n'
+            {
+                i += 1;
+            }
+        }
+        else if c == '/' && next == Some('*')
+        {
+            let end = find(&chars, i + 2, &['*', '/']).ok_or_else(|| rejected("comentário não encerrado"))?;
+            i = end + 2;
+        }
+        else if c == '\''
+        {
+            i = skip_string(&chars, i)?;
+            tokens.push(Token::Literal);
+        }
+        else if matches!(c, 'q' | 'Q') && next == Some('\'')
+        {
+            i = skip_q_string(&chars, i + 1)?;
+            tokens.push(Token::Literal);
+        }
+        else if matches!(c, 'n' | 'N') && next == Some('\'')
+        {
+            i = skip_string(&chars, i + 1)?;
+            tokens.push(Token::Literal);
+        }
+        else if c == '"'
+        {
+            let end = find(&chars, i + 1, &['"']).ok_or_else(|| rejected("identificador entre aspas não encerrado"))?;
+            tokens.push(Token::Quoted(chars[i + 1..end].iter().collect()));
+            i = end + 1;
+        }
+        else if c.is_ascii_digit() || (c == '.' && next.is_some_and(|n| n.is_ascii_digit()))
+        {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.')
+            {
+                i += 1;
+            }
+            tokens.push(Token::Literal);
+        }
+        else if c.is_alphabetic() || c == '_'
+        {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '$' | '#'))
+            {
+                i += 1;
+            }
+            tokens.push(Token::Word(chars[start..i].iter().collect::<String>().to_uppercase()));
+        }
+        else if c == ':' && next.is_some_and(|n| n.is_alphanumeric() || n == '_')
+        {
+            let start = i + 1;
+            i = start;
+            while i < chars.len() && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '$' | '#'))
+            {
+                i += 1;
+            }
+            tokens.push(Token::Bind(chars[start..i].iter().collect()));
+        }
+        else
+        {
+            tokens.push(Token::Symbol(c));
+            i += 1;
+        }
+    }
+    Ok(tokens)
+}
+
+/// Binds do SQL na ordem em que aparecem (com repetições), ignorando literais e comentários.
+pub(super) fn bind_names(sql: &str) -> EngineResult<Vec<String>>
+{
+    Ok(tokenize(sql)?
+        .into_iter()
+        .filter_map(|token| match token
+        {
+            Token::Bind(name) => Some(name),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Posição de `pattern` em `chars` a partir de `from`.
+pub(super) fn find(chars: &[char], from: usize, pattern: &[char]) -> Option<usize>
+{
+    (from..chars.len()).find(|&i| chars[i..].starts_with(pattern))
+}
+
+/// Pula `'...'` (com `''` como aspa escapada) e devolve a posição seguinte.
+pub(super) fn skip_string(chars: &[char], start: usize) -> EngineResult<usize>
+{
+    let mut i = start + 1;
+    loop
+    {
+        match chars.get(i)
+        {
+            None => return Err(rejected("literal de texto não encerrado")),
+            Some('\'') if chars.get(i + 1) == Some(&'\'') => i += 2,
+            Some('\'') => return Ok(i + 1),
+            Some(_) => i += 1,
+        }
+    }
+}
+
+/// Pula `q'[...]'` (qualquer delimitador) a partir da aspa e devolve a posição seguinte.
+pub(super) fn skip_q_string(chars: &[char], quote: usize) -> EngineResult<usize>
+{
+    let open = *chars
+        .get(quote + 1)
+        .ok_or_else(|| rejected("literal de texto não encerrado"))?;
+    let close = match open
+    {
+        '[' => ']',
+        '{' => '}',
+        '(' => ')',
+        '<' => '>',
+        other => other,
+    };
+    find(chars, quote + 2, &[close, '\''])
+        .map(|end| end + 2)
+        .ok_or_else(|| rejected("literal de texto não encerrado"))
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /// Motivo da recusa pelo guard que só permite o schema SYSADM; `None` se aceito.
+    fn rejection(sql: &str) -> Option<String>
+    {
+        match SqlGuard::new().allow_schema("sysadm").unwrap().check(sql)
+        {
+            Ok(()) => None,
+            Err(EngineError::SqlRejected(reason)) => Some(reason),
+            Err(other) => panic!("esperado SqlRejected para {:?}, veio {:?}", sql, other),
+        }
+    }
+
+    #[test]
+    fn tokenize_drops_comments_and_literal_contents()
+    {
+        let tokens = tokenize("select /*+ FULL(t) */ a.\"Nome\", 'x;y', q'[it's]', n'ç', 1.5e3 -- fim;\nfrom t").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Word("SELECT".to_string()),
+                Token::Word("A".to_string()),
+                Token::Symbol('.'),
+                Token::Quoted("Nome".to_string()),
+                Token::Symbol(','),
+                Token::Literal,
+                Token::Symbol(','),
+                Token::Literal,
+                Token::Symbol(','),
+                Token::Literal,
+                Token::Symbol(','),
+                Token::Literal,
+                Token::Word("FROM".to_string()),
+                Token::Word("T".to_string()),
+            ]
+        );
+        assert_eq!(tokenize("SELECT 'it''s' FROM t").unwrap()[1], Token::Literal);
+        assert_eq!(
+            tokenize("SELECT seq$1#x FROM t").unwrap()[1],
+            Token::Word("SEQ$1#X".to_string())
+        );
+    }
+
+    #[test]
+    fn binds_follow_the_order_of_appearance()
+    {
+        assert_eq!(
+            bind_names("SELECT :2, q'[:3]', ':4' /* :5 */ FROM t -- :6\nWHERE a = :1 AND b = :nome AND c = :2").unwrap(),
+            vec!["2", "1", "nome", "2"]
+        );
+        assert_eq!(tokenize("SELECT :1 FROM dual").unwrap()[1], Token::Bind("1".to_string()));
+    }
+
+    #[test]
+    fn tokenize_rejects_unterminated_text()
+    {
+        for sql in [
+            "SELECT 'aberto",
+            "SELECT q'[aberto' FROM t",
+            "SELECT \"aberto",
+            "SELECT /* aberto",
+        ]
+        {
+            assert!(matches!(tokenize(sql), Err(EngineError::SqlRejected(_))), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn accepts_reads_within_allowed_schemas()
+    {
+        for sql in [
+            "SELECT * FROM SYSADM.PS_MMC_CHASSI_LOC WHERE ROWNUM <= 10;",
+            "(SELECT 1 FROM dual)",
+            "WITH x AS (SELECT id FROM sysadm.t) SELECT * FROM x JOIN y ON x.id = y.id",
+            "SELECT EXTRACT(YEAR FROM d), TRIM(' ' FROM nome) FROM t",
+            "SELECT 'DELETE FROM outro.t; DROP' AS texto FROM t -- DROP TABLE t",
+            "SELECT a.b FROM t a, sysadm.u",
+        ]
+        {
+            assert_eq!(rejection(sql), None, "{}", sql);
+        }
+    }
+
+    #[test]
+    fn rejects_writes_and_other_schemas()
+    {
+        assert_eq!(rejection("").as_deref(), Some("instrução vazia"));
+        assert_eq!(
+            rejection("SELECT 1 FROM dual; SELECT 2 FROM dual").as_deref(),
+            Some("mais de uma instrução")
+        );
+        assert!(rejection("DELETE FROM t").unwrap().starts_with("apenas SELECT ou WITH"));
+        assert_eq!(
+            rejection("SELECT seq.NEXTVAL FROM dual").as_deref(),
+            Some("NEXTVAL não é permitido em uma leitura")
+        );
+        assert_eq!(
+            rejection("SELECT * FROM t FOR UPDATE").as_deref(),
+            Some("UPDATE não é permitido em uma leitura")
+        );
+        assert_eq!(
+            rejection("SELECT * FROM t@remoto").as_deref(),
+            Some("database links não são permitidos")
+        );
+        assert_eq!(
+            rejection("SELECT * FROM hr.employees").as_deref(),
+            Some("schema HR fora da lista permitida")
+        );
+        assert_eq!(
+            rejection("SELECT * FROM t, hr.employees").as_deref(),
+            Some("schema HR fora da lista permitida")
+        );
+        assert_eq!(
+            rejection("SELECT * FROM t JOIN \"hr\".x ON 1 = 1").as_deref(),
+            Some("schema hr fora da lista permitida")
+        );
+        assert_eq!(
+            rejection("SELECT hr.pkg.f(1) FROM dual").as_deref(),
+            Some("schema HR fora da lista permitida")
+        );
+        assert_eq!(rejection("SELECT (1 FROM dual").as_deref(), Some("parênteses desbalanceados"));
+    }
+}
@@ -0,0 +1,491 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::fs::File;
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::DateTime;
+use chrono::NaiveDateTime;
+use chrono::NaiveTime;
+use chrono::Utc;
+use log::info;
+use log::warn;
+use polars::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::engine::SqlEngine;
+use super::engine::SqlValue;
+use super::error::EngineError;
+use super::error::EngineResult;
+use super::options::QueryOptions;
+use super::sql::quote_identifier;
+use super::sql::quote_table_name;
+
+/// Nome padrão do arquivo de estado, criado dentro do diretório de saída.
+pub const STATE_FILE_NAME: &str = "_watermarks.json";
+
+/// Coluna usada como marca d'água (high-water mark) da extração.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Watermark
+{
+    /// Coluna crescente: timestamp de alteração ou sequência.
+    Column(String),
+    /// `ORA_ROWSCN` (só Oracle): SCN do último commit que alterou a linha.
+    ///
+    /// Sem `ROWDEPENDENCIES` na tabela o SCN é por bloco, então linhas vizinhas de uma linha
+    /// alterada também são reexportadas.
+    OraRowscn,
+}
+
+impl Watermark
+{
+    /// Expressão SQL da marca d'água.
+    fn expression(&self) -> EngineResult<String>
+    {
+        match self
+        {
+            Watermark::Column(column) => quote_identifier(column),
+            Watermark::OraRowscn => Ok("ORA_ROWSCN".to_string()),
+        }
+    }
+
+    /// Colunas do SELECT: com `ORA_ROWSCN`, o SCN vai junto no arquivo.
+    fn projection(&self) -> &'static str
+    {
+        match self
+        {
+            Watermark::Column(_) => "t.*",
+            Watermark::OraRowscn => "t.*, ORA_ROWSCN AS WATERMARK_SCN",
+        }
+    }
+}
+
+/// Valor da marca d'água gravado no arquivo de estado.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum WatermarkValue
+{
+    Int(i64),
+    /// Número com casas decimais, como lido de uma coluna `FLOAT`/`BINARY_DOUBLE`.
+    Float(f64),
+    /// Timestamp com a precisão da coluna de origem (até nanossegundos).
+    Datetime(NaiveDateTime),
+    Text(String),
+}
+
+impl From<WatermarkValue> for SqlValue
+{
+    fn from(value: WatermarkValue) -> Self
+    {
+        match value
+        {
+            WatermarkValue::Int(v) => SqlValue::Int(v),
+            WatermarkValue::Float(v) => SqlValue::Float(v),
+            WatermarkValue::Datetime(v) => SqlValue::Datetime(v),
+            WatermarkValue::Text(v) => SqlValue::Text(v),
+        }
+    }
+}
+
+/// Execução gravada mas ainda não confirmada no estado; permite retomar sem duplicar linhas.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingRun
+{
+    pub file: String,
+    pub high: WatermarkValue,
+    pub rows: u64,
+}
+
+/// Estado de uma tabela no arquivo de estado.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TableState
+{
+    pub watermark: Option<WatermarkValue>,
+    pub pending: Option<PendingRun>,
+    pub last_file: Option<String>,
+    pub total_rows: u64,
+}
+
+/// Conteúdo do arquivo de estado: uma entrada por tabela extraída.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExtractState
+{
+    pub tables: BTreeMap<String, TableState>,
+}
+
+impl ExtractState
+{
+    /// Lê o estado; um arquivo inexistente equivale a estado vazio.
+    pub fn load(path: &Path) -> EngineResult<Self>
+    {
+        if !path.exists()
+        {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(path).map_err(|e| EngineError::io(path, e))?;
+        serde_json::from_str(&text).map_err(|source| EngineError::State {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Grava o estado em um arquivo temporário e o renomeia, para nunca deixar um JSON pela metade.
+    pub fn save(&self, path: &Path) -> EngineResult<()>
+    {
+        let text = serde_json::to_string_pretty(self).map_err(|source| EngineError::State {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, text).map_err(|e| EngineError::io(&tmp, e))?;
+        fs::rename(&tmp, path).map_err(|e| EngineError::io(path, e))
+    }
+}
+
+/// Resultado de uma execução do extrator.
+#[derive(Debug, Clone)]
+pub struct ExtractReport
+{
+    /// Arquivo Parquet gerado (`None` quando não havia linhas novas).
+    pub file: Option<PathBuf>,
+    pub rows: u64,
+    pub low: Option<WatermarkValue>,
+    pub high: Option<WatermarkValue>,
+}
+
+/// Extração incremental de uma tabela para arquivos Parquet, guiada por uma marca d'água.
+///
+/// Cada execução lê apenas as linhas com marca entre a última gravada (exclusive) e o máximo
+/// atual da tabela (inclusive), e as grava em um novo arquivo Parquet no diretório de saída.
+/// Linhas commitadas depois com marca menor que a já registrada não são capturadas.
+///
+/// O arquivo é escrito como `.tmp` e só então renomeado; o estado registra a execução como
+/// pendente antes do rename e a confirma depois. Uma execução interrompida em qualquer ponto é
+/// retomada ou descartada na seguinte, sem duplicar linhas.
+#[derive(Debug, Clone)]
+pub struct IncrementalExtract
+{
+    pub table: String,
+    pub watermark: Watermark,
+    pub output_dir: PathBuf,
+    pub state_file: PathBuf,
+    /// Filtro adicional, sem o `WHERE` (ex.: `"STATUS = 'A'"`).
+    pub filter: Option<String>,
+    pub chunk_rows: usize,
+    pub options: QueryOptions,
+}
+
+impl IncrementalExtract
+{
+    pub fn new(table: impl Into<String>, watermark: Watermark, output_dir: impl Into<PathBuf>) -> Self
+    {
+        let output_dir = output_dir.into();
+        Self {
+            table: table.into(),
+            watermark,
+            state_file: output_dir.join(STATE_FILE_NAME),
+            output_dir,
+            filter: None,
+            chunk_rows: 100_000,
+            options: QueryOptions::default(),
+        }
+    }
+
+    pub fn state_file(mut self, path: impl Into<PathBuf>) -> Self
+    {
+        self.state_file = path.into();
+        self
+    }
+
+    pub fn filter(mut self, filter: impl Into<String>) -> Self
+    {
+        self.filter = Some(filter.into());
+        self
+    }
+
+    pub fn chunk_rows(mut self, chunk_rows: usize) -> Self
+    {
+        self.chunk_rows = chunk_rows;
+        self
+    }
+
+    pub fn options(mut self, options: QueryOptions) -> Self
+    {
+        self.options = options;
+        self
+    }
+
+    /// Executa uma rodada da extração.
+    pub fn run(&self, engine: &dyn SqlEngine) -> EngineResult<ExtractReport>
+    {
+        fs::create_dir_all(&self.output_dir).map_err(|e| EngineError::io(&self.output_dir, e))?;
+
+        let key = self.state_key()?;
+        let mut state = ExtractState::load(&self.state_file)?;
+        self.recover(&mut state, &key)?;
+
+        let low = state.tables.get(&key).and_then(|t| t.watermark.clone());
+        let Some(high) = self.current_high(engine)?
+        else
+        {
+            info!("{}: tabela sem linhas; nada a extrair.", self.table);
+            return Ok(ExtractReport {
+                file: None,
+                rows: 0,
+                low: low.clone(),
+                high: low,
+            });
+        };
+        if low.as_ref() == Some(&high)
+        {
+            info!("{}: nenhuma linha nova desde {:?}.", self.table, high);
+            return Ok(ExtractReport {
+                file: None,
+                rows: 0,
+                low,
+                high: Some(high),
+            });
+        }
+
+        let file_name = format!("{}_{}.parquet", key.replace('"', ""), Utc::now().format("%Y%m%dT%H%M%S%3f"));
+        let final_path = self.output_dir.join(&file_name);
+        let tmp_path = self.output_dir.join(format!("{}.tmp", file_name));
+
+        let rows = match self.write_window(engine, low.clone(), high.clone(), &tmp_path)
+        {
+            Ok(rows) => rows,
+            Err(e) =>
+            {
+                let _ = fs::remove_file(&tmp_path);
+                return Err(e);
+            },
+        };
+        if rows == 0
+        {
+            let _ = fs::remove_file(&tmp_path);
+            info!("{}: nenhuma linha nova até {:?}.", self.table, high);
+            return Ok(ExtractReport {
+                file: None,
+                rows: 0,
+                low,
+                high: Some(high),
+            });
+        }
+
+        // 1) registra a execução pendente; 2) publica o arquivo; 3) confirma a marca d'água
+        let table_state = state.tables.entry(key.clone()).or_default();
+        table_state.pending = Some(PendingRun {
+            file: file_name.clone(),
+            high: high.clone(),
+            rows,
+        });
+        state.save(&self.state_file)?;
+
+        fs::rename(&tmp_path, &final_path).map_err(|e| EngineError::io(&final_path, e))?;
+        self.recover(&mut state, &key)?;
+
+        info!("{}: {} linhas gravadas em {}.", self.table, rows, final_path.display());
+        Ok(ExtractReport {
+            file: Some(final_path),
+            rows,
+            low,
+            high: Some(high),
+        })
+    }
+
+    /// Conclui ou descarta uma execução pendente deixada por uma rodada anterior.
+    fn recover(&self, state: &mut ExtractState, key: &str) -> EngineResult<()>
+    {
+        let Some(table_state) = state.tables.get_mut(key)
+        else
+        {
+            return Ok(());
+        };
+        let Some(pending) = table_state.pending.take()
+        else
+        {
+            return Ok(());
+        };
+
+        let final_path = self.output_dir.join(&pending.file);
+        let tmp_path = self.output_dir.join(format!("{}.tmp", pending.file));
+        if !final_path.exists() && tmp_path.exists()
+        {
+            fs::rename(&tmp_path, &final_path).map_err(|e| EngineError::io(&final_path, e))?;
+        }
+
+        if final_path.exists()
+        {
+            table_state.watermark = Some(pending.high);
+            table_state.last_file = Some(pending.file);
+            table_state.total_rows += pending.rows;
+        }
+        else
+        {
+            warn!(
+                "{}: arquivo pendente {} não encontrado; a janela será extraída de novo.",
+                self.table, pending.file
+            );
+        }
+        state.save(&self.state_file)
+    }
+
+    /// Maior valor atual da marca d'água na tabela (`None` se a tabela estiver vazia).
+    fn current_high(&self, engine: &dyn SqlEngine) -> EngineResult<Option<WatermarkValue>>
+    {
+        let sql = format!(
+            "SELECT MAX({}) AS HIGH FROM {} t{}",
+            self.watermark.expression()?,
+            quote_table_name(&self.table)?,
+            self.filter.as_ref().map(|f| format!(" WHERE {}", f)).unwrap_or_default()
+        );
+        let df = engine.query_df(&sql, &[], &QueryOptions::default(), None)?;
+        let column = df.get_columns().first().ok_or_else(|| EngineError::Conversion {
+            column: "HIGH".to_string(),
+            row: 1,
+            message: "consulta do máximo não retornou colunas".to_string(),
+            sql_id: None,
+        })?;
+        watermark_value(column.as_materialized_series()).map_err(|e| e.with_sql(&sql))
+    }
+
+    /// Grava as linhas da janela `(low, high]` em `path` e devolve quantas foram gravadas.
+    fn write_window(
+        &self,
+        engine: &dyn SqlEngine,
+        low: Option<WatermarkValue>,
+        high: WatermarkValue,
+        path: &Path,
+    ) -> EngineResult<u64>
+    {
+        let expression = self.watermark.expression()?;
+        let mut conditions = Vec::new();
+        let mut params = Vec::new();
+        if let Some(low) = low
+        {
+            params.push(SqlValue::from(low));
+            conditions.push(format!("{} > :{}", expression, params.len()));
+        }
+        params.push(SqlValue::from(high));
+        conditions.push(format!("{} <= :{}", expression, params.len()));
+        if let Some(filter) = &self.filter
+        {
+            conditions.push(format!("({})", filter));
+        }
+
+        let sql = format!(
+            "SELECT {} FROM {} t WHERE {} ORDER BY {}",
+            self.watermark.projection(),
+            quote_table_name(&self.table)?,
+            conditions.join(" AND "),
+            expression
+        );
+
+        let mut writer = None;
+        let mut rows = 0u64;
+        for chunk in engine.query_df_stream(&sql, &params, self.chunk_rows, &self.options)?
+        {
+            let chunk = chunk?;
+            if chunk.height() == 0
+            {
+                continue;
+            }
+            if writer.is_none()
+            {
+                let file = File::create(path).map_err(|e| EngineError::io(path, e))?;
+                writer = Some(ParquetWriter::new(file).batched(chunk.schema())?);
+            }
+            if let Some(writer) = writer.as_mut()
+            {
+                writer.write_batch(&chunk)?;
+            }
+            rows += chunk.height() as u64;
+        }
+        if let Some(writer) = writer
+        {
+            writer.finish()?;
+        }
+        Ok(rows)
+    }
+
+    /// Chave da tabela no arquivo de estado.
+    fn state_key(&self) -> EngineResult<String>
+    {
+        Ok(quote_table_name(&self.table)?.to_lowercase())
+    }
+}
+
+/// Lê o único valor da coluna `HIGH` como marca d'água, sem perder precisão.
+///
+/// Timestamps são lidos na unidade da própria coluna (até nanossegundos). Números inteiros,
+/// inclusive o `FLOAT` sem casas que o `MAX` de uma coluna NUMBER devolve, viram `Int`; os
+/// demais `FLOAT` ficam como `Float`. `Decimal` com casas decimais é recusado: convertê-lo em
+/// `f64` poderia perder dígitos e pular ou repetir linhas na próxima janela.
+fn watermark_value(series: &Series) -> EngineResult<Option<WatermarkValue>>
+{
+    let value = match series.dtype()
+    {
+        DataType::Datetime(unit, _) =>
+        {
+            let from_timestamp = match unit
+            {
+                TimeUnit::Milliseconds => DateTime::from_timestamp_millis,
+                TimeUnit::Microseconds => DateTime::from_timestamp_micros,
+                TimeUnit::Nanoseconds => |ns| Some(DateTime::from_timestamp_nanos(ns)),
+            };
+            series
+                .datetime()?
+                .get(0)
+                .and_then(from_timestamp)
+                .map(|dt| WatermarkValue::Datetime(dt.naive_utc()))
+        },
+        DataType::Date => series
+            .date()?
+            .as_date_iter()
+            .next()
+            .flatten()
+            .map(|date| WatermarkValue::Datetime(date.and_time(NaiveTime::MIN))),
+        dtype if dtype.is_integer() || decimal_scale(dtype) == Some(0) =>
+        {
+            series.strict_cast(&DataType::Int64)?.i64()?.get(0).map(WatermarkValue::Int)
+        },
+        dtype if dtype.is_float() =>
+        {
+            series.cast(&DataType::Float64)?.f64()?.get(0).map(|v| {
+                // Faixa em que a conversão para i64 é exata
+                if v.fract() == 0.0 && v.abs() < 9_007_199_254_740_992.0
+                {
+                    WatermarkValue::Int(v as i64)
+                }
+                else
+                {
+                    WatermarkValue::Float(v)
+                }
+            })
+        },
+        dtype if dtype.is_decimal() =>
+        {
+            return Err(EngineError::InvalidArgument(format!(
+                "marca d'água do tipo {} tem casas decimais; use uma coluna inteira, FLOAT ou de data",
+                dtype
+            )));
+        },
+        _ => series
+            .cast(&DataType::String)?
+            .str()?
+            .get(0)
+            .map(|v| WatermarkValue::Text(v.to_string())),
+    };
+    Ok(value)
+}
+
+/// Escala de uma coluna `Decimal`, quando conhecida.
+fn decimal_scale(dtype: &DataType) -> Option<usize>
+{
+    match dtype
+    {
+        DataType::Decimal(_, scale) => *scale,
+        _ => None,
+    }
+}
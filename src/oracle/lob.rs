@@ -0,0 +1,322 @@
+use std::fs;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use oracle::sql_type::Blob;
+use oracle::sql_type::Clob;
+use oracle::sql_type::Nclob;
+use oracle::sql_type::OracleType;
+use oracle::Row;
+use polars::prelude::DataType;
+
+use super::types::ColumnBuffer;
+
+type LobResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+/// Tamanho do buffer usado ao ler LOBs em partes.
+const READ_BUFFER: usize = 64 * 1024;
+
+/// Como ler colunas grandes: `CLOB`, `NCLOB` e `LONG` (texto) e `BLOB`, `RAW` e `LONG RAW` (binárias).
+///
+/// As de texto viram `String` e as binárias `Binary`, exceto em `File`, que grava o conteúdo
+/// em disco e devolve o caminho como `String`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum LobMode
+{
+    /// Valor inteiro na coluna.
+    #[default]
+    Inline,
+    /// No máximo este número de caracteres (texto) ou bytes (binário); o restante não é lido.
+    Truncate(usize),
+    /// Grava cada valor em `<dir>/<COLUNA>_<consulta>_<linha>.txt` (ou `.bin`) e guarda o
+    /// caminho. `<consulta>` identifica cada execução (inclusive cada partição), para que elas
+    /// não se sobreponham; caracteres fora de `[A-Za-z0-9_$#-]` no nome da coluna viram `_`.
+    /// Um arquivo existente com o mesmo nome é erro, nunca sobrescrito.
+    File(PathBuf),
+    /// Não lê o conteúdo; a coluna fica toda nula.
+    Skip,
+}
+
+impl LobMode
+{
+    /// Modos que precisam do LOB locator para não trazer o conteúdo inteiro para a memória.
+    pub(crate) fn needs_locator(&self) -> bool
+    {
+        matches!(self, LobMode::Truncate(_) | LobMode::File(_))
+    }
+}
+
+/// Origem do conteúdo de uma coluna grande.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LobKind
+{
+    Clob,
+    Nclob,
+    Blob,
+    /// `LONG`: o driver sempre entrega o valor inteiro.
+    Long,
+    /// `RAW` e `LONG RAW`, também entregues inteiros.
+    Raw,
+}
+
+impl LobKind
+{
+    fn of(oracle_type: &OracleType) -> Option<Self>
+    {
+        match oracle_type
+        {
+            OracleType::CLOB => Some(LobKind::Clob),
+            OracleType::NCLOB => Some(LobKind::Nclob),
+            OracleType::BLOB => Some(LobKind::Blob),
+            OracleType::Long => Some(LobKind::Long),
+            OracleType::Raw(_) | OracleType::LongRaw => Some(LobKind::Raw),
+            _ => None,
+        }
+    }
+
+    fn is_binary(self) -> bool
+    {
+        matches!(self, LobKind::Blob | LobKind::Raw)
+    }
+}
+
+/// Leitor de uma coluna grande conforme o `LobMode` escolhido para ela.
+#[derive(Debug, Clone)]
+pub(crate) struct LobColumn
+{
+    column: String,
+    kind: LobKind,
+    mode: LobMode,
+    /// Com o locator ativo no statement, `CLOB`/`BLOB` podem ser lidos em partes.
+    locator: bool,
+    /// Prefixo dos arquivos de `LobMode::File`: coluna saneada e identificador da consulta.
+    file_prefix: String,
+}
+
+impl LobColumn
+{
+    /// Leitor para a coluna, ou `None` quando ela não é de um tipo grande ou o modo é `Inline`
+    /// (caso em que a leitura comum já serve).
+    ///
+    /// `dtype` é o tipo de destino: em `FetchMode::Strings`, colunas binárias continuam
+    /// sendo lidas como texto e ficam de fora.
+    pub(crate) fn new(
+        column: &str,
+        oracle_type: &OracleType,
+        dtype: &DataType,
+        mode: &LobMode,
+        locator: bool,
+    ) -> Option<Self>
+    {
+        let kind = LobKind::of(oracle_type)?;
+        let expected = match mode
+        {
+            LobMode::File(_) => DataType::String,
+            _ if kind.is_binary() => DataType::Binary,
+            _ => DataType::String,
+        };
+        if *mode == LobMode::Inline || *dtype != expected
+        {
+            return None;
+        }
+
+        let file_prefix = match mode
+        {
+            LobMode::File(_) => format!("{}_{}", file_safe(column), query_run_id()),
+            _ => String::new(),
+        };
+        Some(Self {
+            column: column.to_string(),
+            kind,
+            mode: mode.clone(),
+            locator,
+            file_prefix,
+        })
+    }
+
+    /// Tipo Polars da coluna no modo escolhido.
+    pub(crate) fn dtype(oracle_type: &OracleType, mode: &LobMode, default: DataType) -> DataType
+    {
+        match (LobKind::of(oracle_type), mode)
+        {
+            (Some(_), LobMode::File(_)) => DataType::String,
+            _ => default,
+        }
+    }
+
+    /// Lê a coluna `idx` da linha `row_no` (a partir de 1) e acrescenta o valor ao buffer.
+    pub(crate) fn push(&self, buffer: &mut ColumnBuffer, row: &Row, idx: usize, row_no: usize) -> LobResult<()>
+    {
+        match (&self.mode, buffer)
+        {
+            (LobMode::Skip, ColumnBuffer::Utf8(values)) => values.push(None),
+            (LobMode::Skip, ColumnBuffer::Binary(values)) => values.push(None),
+            (LobMode::Truncate(max), ColumnBuffer::Utf8(values)) => values.push(self.read_text(row, idx, *max)?),
+            (LobMode::Truncate(max), ColumnBuffer::Binary(values)) => values.push(self.read_binary(row, idx, *max)?),
+            (LobMode::File(dir), ColumnBuffer::Utf8(values)) => values.push(self.write_file(row, idx, row_no, dir)?),
+            (mode, _) => return Err(format!("modo {:?} incompatível com a coluna {}", mode, self.column).into()),
+        }
+        Ok(())
+    }
+
+    /// Até `max` caracteres do texto.
+    fn read_text(&self, row: &Row, idx: usize, max: usize) -> LobResult<Option<String>>
+    {
+        let mut reader: Box<dyn Read> = match (self.kind, self.locator)
+        {
+            (LobKind::Clob, true) => match row.get::<_, Option<Clob>>(idx)?
+            {
+                Some(clob) => Box::new(clob),
+                None => return Ok(None),
+            },
+            (LobKind::Nclob, true) => match row.get::<_, Option<Nclob>>(idx)?
+            {
+                Some(nclob) => Box::new(nclob),
+                None => return Ok(None),
+            },
+            _ =>
+            {
+                let text: Option<String> = row.get(idx)?;
+                return Ok(text.map(|t| truncate_chars(t, max)));
+            },
+        };
+
+        // O driver só devolve caracteres inteiros, então cada parte é UTF-8 válido
+        let mut text = String::new();
+        let mut chars = 0;
+        let mut buf = vec![0u8; READ_BUFFER];
+        while chars < max
+        {
+            let read = reader.read(&mut buf)?;
+            if read == 0
+            {
+                break;
+            }
+            let part = std::str::from_utf8(&buf[..read])?;
+            chars += part.chars().count();
+            text.push_str(part);
+        }
+        Ok(Some(truncate_chars(text, max)))
+    }
+
+    /// Até `max` bytes do valor binário.
+    fn read_binary(&self, row: &Row, idx: usize, max: usize) -> LobResult<Option<Vec<u8>>>
+    {
+        if self.kind == LobKind::Blob && self.locator
+        {
+            let Some(blob) = row.get::<_, Option<Blob>>(idx)?
+            else
+            {
+                return Ok(None);
+            };
+            let mut bytes = Vec::new();
+            blob.take(max as u64).read_to_end(&mut bytes)?;
+            return Ok(Some(bytes));
+        }
+
+        let bytes: Option<Vec<u8>> = row.get(idx)?;
+        Ok(bytes.map(|mut b| {
+            b.truncate(max);
+            b
+        }))
+    }
+
+    /// Copia o valor para um arquivo em `dir`, em partes quando há locator, e devolve o caminho.
+    fn write_file(&self, row: &Row, idx: usize, row_no: usize, dir: &Path) -> LobResult<Option<String>>
+    {
+        let mut reader: Box<dyn Read> = match (self.kind, self.locator)
+        {
+            (LobKind::Clob, true) => match row.get::<_, Option<Clob>>(idx)?
+            {
+                Some(clob) => Box::new(clob),
+                None => return Ok(None),
+            },
+            (LobKind::Nclob, true) => match row.get::<_, Option<Nclob>>(idx)?
+            {
+                Some(nclob) => Box::new(nclob),
+                None => return Ok(None),
+            },
+            (LobKind::Blob, true) => match row.get::<_, Option<Blob>>(idx)?
+            {
+                Some(blob) => Box::new(blob),
+                None => return Ok(None),
+            },
+            (kind, _) if kind.is_binary() => match row.get::<_, Option<Vec<u8>>>(idx)?
+            {
+                Some(bytes) => Box::new(io::Cursor::new(bytes)),
+                None => return Ok(None),
+            },
+            _ => match row.get::<_, Option<String>>(idx)?
+            {
+                Some(text) => Box::new(io::Cursor::new(text.into_bytes())),
+                None => return Ok(None),
+            },
+        };
+
+        fs::create_dir_all(dir).map_err(|e| format!("falha ao criar {}: {}", dir.display(), e))?;
+        let extension = if self.kind.is_binary() { "bin" } else { "txt" };
+        let path = dir.join(format!("{}_{}.{}", self.file_prefix, row_no, extension));
+
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .map_err(|e| format!("falha ao criar {}: {}", path.display(), e))?;
+        let mut file = BufWriter::new(file);
+        io::copy(&mut reader, &mut file)?;
+        file.flush()?;
+
+        Ok(Some(path.to_string_lossy().into_owned()))
+    }
+}
+
+/// Nome da coluna utilizável em nome de arquivo: sem separadores de caminho nem `..`.
+fn file_safe(column: &str) -> String
+{
+    let name: String = column
+        .chars()
+        .map(|c| match c
+        {
+            'A'..='Z' | 'a'..='z' | '0'..='9' | '_' | '$' | '#' | '-' => c,
+            _ => '_',
+        })
+        .collect();
+    if name.is_empty()
+    {
+        "COLUNA".to_string()
+    }
+    else
+    {
+        name
+    }
+}
+
+/// Identificador da consulta nos nomes de arquivo: instante em ms e uma sequência do processo,
+/// distinto entre consultas e partições lidas ao mesmo tempo.
+fn query_run_id() -> String
+{
+    static SEQ: AtomicU64 = AtomicU64::new(0);
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default();
+    format!("{}-{}", millis, SEQ.fetch_add(1, Ordering::Relaxed))
+}
+
+fn truncate_chars(mut text: String, max: usize) -> String
+{
+    if let Some((end, _)) = text.char_indices().nth(max)
+    {
+        text.truncate(end);
+    }
+    text
+}
@@ -1 +1,29 @@
+pub mod cache;
+pub mod cancel;
+pub mod catalog;
+pub mod config;
 pub mod connection;
+pub mod ddl;
+pub mod engine;
+pub mod error;
+pub mod export;
+pub mod guard;
+pub mod incremental;
+pub mod lob;
+pub mod options;
+pub mod partition;
+pub mod plan;
+pub mod pool;
+pub mod procedure;
+pub mod registry;
+pub mod retry;
+pub mod rows;
+pub mod session;
+pub mod sql;
+pub mod sqlite;
+pub mod stream;
+#[cfg(test)]
+mod testing;
+pub mod transaction;
+pub mod types;
+pub mod write;
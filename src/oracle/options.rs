@@ -0,0 +1,224 @@
+use std::time::Duration;
+
+use oracle::ColumnInfo;
+use polars::prelude::DataType;
+use polars::prelude::Schema;
+
+use super::lob::LobColumn;
+use super::lob::LobMode;
+use super::plan::CostLimit;
+use super::session::SessionTag;
+use super::types::polars_dtype;
+use super::types::reads_natively;
+use super::types::DateMapping;
+
+/// Define como as colunas retornadas pelo Oracle são convertidas em colunas do Polars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FetchMode
+{
+    /// Inspeciona `ColumnInfo::oracle_type()` e monta colunas tipadas.
+    #[default]
+    Typed,
+    /// Lê toda célula como `Option<String>` (comportamento original, útil como fallback).
+    Strings,
+}
+
+/// Opções de uma consulta executada pelo `EngineOracle`.
+#[derive(Debug, Clone, Default)]
+pub struct QueryOptions
+{
+    pub mode: FetchMode,
+    pub date_mapping: DateMapping,
+    /// Colunas (nome sem diferenciar maiúsculas) lidas como `Boolean`, como flags `Y`/`N` ou `NUMBER(1)`.
+    pub boolean_columns: Vec<String>,
+    /// Linhas buscadas do servidor por round-trip (padrão do driver: 100).
+    pub fetch_array_size: Option<u32>,
+    /// Linhas pré-carregadas pelo Oracle Client junto com a execução (padrão do driver: 2).
+    pub prefetch_rows: Option<u32>,
+    /// Validade do resultado no `CachedEngine` (padrão: `CacheConfig::default_ttl`; zero ignora o cache).
+    pub cache_ttl: Option<Duration>,
+    /// Tempo máximo da consulta no Oracle (padrão: `OracleConfig::call_timeout`).
+    pub timeout: Option<Duration>,
+    /// Leitura das colunas CLOB, BLOB, LONG e RAW sem modo próprio em `lob_columns`.
+    pub lob_mode: LobMode,
+    /// Modo de leitura por coluna grande (nome sem diferenciar maiúsculas).
+    pub lob_columns: Vec<(String, LobMode)>,
+    /// Tipo forçado por coluna (nome sem diferenciar maiúsculas), no lugar do tipo inferido.
+    pub column_types: Vec<(String, DataType)>,
+    /// `MODULE`, `ACTION` e `CLIENT_IDENTIFIER` da sessão durante a consulta (só no Oracle).
+    pub session: SessionTag,
+    /// Limite de custo do plano conferido antes da consulta (padrão: `OracleConfig::cost_limit`; só no Oracle).
+    pub cost_limit: Option<CostLimit>,
+}
+
+impl QueryOptions
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    pub fn mode(mut self, mode: FetchMode) -> Self
+    {
+        self.mode = mode;
+        self
+    }
+
+    pub fn date_mapping(mut self, date_mapping: DateMapping) -> Self
+    {
+        self.date_mapping = date_mapping;
+        self
+    }
+
+    pub fn boolean_column(mut self, name: impl Into<String>) -> Self
+    {
+        self.boolean_columns.push(name.into());
+        self
+    }
+
+    pub fn fetch_array_size(mut self, size: u32) -> Self
+    {
+        self.fetch_array_size = Some(size);
+        self
+    }
+
+    pub fn prefetch_rows(mut self, rows: u32) -> Self
+    {
+        self.prefetch_rows = Some(rows);
+        self
+    }
+
+    pub fn cache_ttl(mut self, ttl: Duration) -> Self
+    {
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self
+    {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn lob_mode(mut self, mode: LobMode) -> Self
+    {
+        self.lob_mode = mode;
+        self
+    }
+
+    pub fn lob_column(mut self, name: impl Into<String>, mode: LobMode) -> Self
+    {
+        self.lob_columns.push((name.into(), mode));
+        self
+    }
+
+    /// Força o tipo Polars da coluna, como `String` em códigos de chassi guardados em `NUMBER`
+    /// ou `Date` em datas guardadas em `VARCHAR2`.
+    ///
+    /// A conversão é feita durante o fetch; um valor que não couber no tipo gera
+    /// `EngineError::Conversion` com a coluna e a linha.
+    pub fn column_type(mut self, name: impl Into<String>, dtype: DataType) -> Self
+    {
+        self.column_types.push((name.into(), dtype));
+        self
+    }
+
+    /// Força o tipo de cada coluna do `Schema` parcial; as demais seguem a inferência.
+    pub fn schema_hints(mut self, schema: &Schema) -> Self
+    {
+        self.column_types
+            .extend(schema.iter().map(|(name, dtype)| (name.to_string(), dtype.clone())));
+        self
+    }
+
+    pub fn cost_limit(mut self, cost_limit: CostLimit) -> Self
+    {
+        self.cost_limit = Some(cost_limit);
+        self
+    }
+
+    pub fn module(mut self, module: impl Into<String>) -> Self
+    {
+        self.session = self.session.module(module);
+        self
+    }
+
+    pub fn action(mut self, action: impl Into<String>) -> Self
+    {
+        self.session = self.session.action(action);
+        self
+    }
+
+    pub fn client_identifier(mut self, client_identifier: impl Into<String>) -> Self
+    {
+        self.session = self.session.client_identifier(client_identifier);
+        self
+    }
+
+    /// Tipo forçado para a coluna, se houver.
+    pub fn type_hint(&self, name: &str) -> Option<&DataType>
+    {
+        self.column_types
+            .iter()
+            .rev()
+            .find(|(column, _)| column.eq_ignore_ascii_case(name))
+            .map(|(_, dtype)| dtype)
+    }
+
+    /// Modo de leitura da coluna grande: o definido para ela ou, na falta, `lob_mode`.
+    pub fn lob_mode_for(&self, name: &str) -> &LobMode
+    {
+        self.lob_columns
+            .iter()
+            .rev()
+            .find(|(column, _)| column.eq_ignore_ascii_case(name))
+            .map(|(_, mode)| mode)
+            .unwrap_or(&self.lob_mode)
+    }
+
+    /// Se algum modo exige buscar os LOBs como locator.
+    pub(crate) fn needs_lob_locator(&self) -> bool
+    {
+        self.lob_mode.needs_locator() || self.lob_columns.iter().any(|(_, mode)| mode.needs_locator())
+    }
+
+    /// Tipo Polars de destino para a coluna, considerando o modo e as colunas configuradas.
+    pub fn dtype_for(&self, column: &ColumnInfo) -> DataType
+    {
+        if let Some(dtype) = self.type_hint(column.name())
+        {
+            return dtype.clone();
+        }
+        self.inferred_dtype(column)
+    }
+
+    /// Tipo em que a coluna é lida do driver: o de destino ou, quando ele não pode ser lido
+    /// direto (texto para número, por exemplo), o inferido, convertido depois por `cast_series`.
+    pub(crate) fn read_dtype_for(&self, column: &ColumnInfo) -> DataType
+    {
+        match self.type_hint(column.name())
+        {
+            Some(dtype) if reads_natively(column.oracle_type(), dtype) => dtype.clone(),
+            _ => self.inferred_dtype(column),
+        }
+    }
+
+    fn inferred_dtype(&self, column: &ColumnInfo) -> DataType
+    {
+        match self.mode
+        {
+            FetchMode::Strings => DataType::String,
+            FetchMode::Typed if self.is_boolean_column(column.name()) => DataType::Boolean,
+            FetchMode::Typed => LobColumn::dtype(
+                column.oracle_type(),
+                self.lob_mode_for(column.name()),
+                polars_dtype(column.oracle_type(), self.date_mapping),
+            ),
+        }
+    }
+
+    pub(crate) fn is_boolean_column(&self, name: &str) -> bool
+    {
+        self.boolean_columns.iter().any(|c| c.eq_ignore_ascii_case(name))
+    }
+}
@@ -0,0 +1,617 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use chrono::DateTime;
+use log::info;
+use log::warn;
+use polars::prelude::*;
+
+use super::cancel::CancelToken;
+use super::connection::EngineOracle;
+use super::engine::SqlEngine;
+use super::engine::SqlValue;
+use super::error::EngineError;
+use super::error::EngineResult;
+use super::export::export_query;
+use super::export::ExportFormat;
+use super::export::ExportOptions;
+use super::options::QueryOptions;
+use super::sql::quote_identifier;
+use super::sql::quote_table_name;
+
+/// Resultado de uma partição: tempo, linhas e o valor produzido por ela.
+type PartitionResult<T> = EngineResult<(PartitionTiming, T)>;
+
+/// Como dividir a tabela em partições.
+///
+/// Todas as estratégias cobrem a tabela inteira: cada linha cai em exatamente uma partição,
+/// inclusive as com a coluna nula.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartitionStrategy
+{
+    /// Faixas contíguas de `ROWID`, com cortes calculados por `NTILE` sobre os ROWIDs da tabela
+    /// (uma leitura completa antes da extração, mas sem exigir acesso a `DBA_EXTENTS`).
+    RowidRange,
+    /// `ORA_HASH(coluna, N - 1) = k`: partições de tamanho parecido mesmo com dados enviesados,
+    /// mas cada uma lê a tabela inteira. Use `"ROWID"` para distribuir por linha.
+    OraHash(String),
+    /// Faixas de mesmo tamanho entre o mínimo e o máximo de uma coluna numérica ou de data.
+    ColumnRange(String),
+}
+
+/// Extração de uma tabela em partições lidas em paralelo, cada uma por uma conexão.
+///
+/// Com conexão única as consultas disputam a mesma sessão e acabam em série; configure um
+/// pool (`OracleConfig::pool`) com ao menos `concurrency` conexões.
+///
+/// ```ignore
+/// let extract = PartitionedExtract::new("SYSADM.PS_MMC_CHASSI_LOC", PartitionStrategy::RowidRange, 8);
+/// let (df, report) = extract.to_df(&engine)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct PartitionedExtract
+{
+    pub table: String,
+    pub strategy: PartitionStrategy,
+    pub partitions: usize,
+    /// Partições lidas ao mesmo tempo (padrão: todas).
+    pub concurrency: usize,
+    /// Filtro adicional, sem o `WHERE` (ex.: `"STATUS = 'A'"`).
+    pub filter: Option<String>,
+    /// Linhas por bloco gravado em `to_parquet`.
+    pub chunk_rows: usize,
+    pub compression: ParquetCompression,
+    pub options: QueryOptions,
+}
+
+/// Uma partição: o predicado sobre a tabela e seus binds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Partition
+{
+    /// Posição da partição, a partir de 0.
+    pub index: usize,
+    /// Condição da partição, sem o `WHERE` (vazia quando há uma única partição).
+    pub predicate: String,
+    pub params: Vec<SqlValue>,
+}
+
+/// Tempo e volume de uma partição.
+#[derive(Debug, Clone)]
+pub struct PartitionTiming
+{
+    pub index: usize,
+    pub predicate: String,
+    pub rows: u64,
+    pub elapsed: Duration,
+    /// Arquivo gravado, em `to_parquet`.
+    pub file: Option<PathBuf>,
+}
+
+/// Resultado de uma extração particionada.
+#[derive(Debug, Clone)]
+pub struct PartitionedReport
+{
+    /// Uma entrada por partição, na ordem das partições.
+    pub partitions: Vec<PartitionTiming>,
+    pub rows: u64,
+    /// Tempo total, incluindo o cálculo das partições.
+    pub elapsed: Duration,
+}
+
+impl PartitionedExtract
+{
+    pub fn new(table: impl Into<String>, strategy: PartitionStrategy, partitions: usize) -> Self
+    {
+        Self {
+            table: table.into(),
+            strategy,
+            partitions,
+            concurrency: partitions,
+            filter: None,
+            chunk_rows: 100_000,
+            compression: ParquetCompression::Zstd(None),
+            options: QueryOptions::default(),
+        }
+    }
+
+    pub fn concurrency(mut self, concurrency: usize) -> Self
+    {
+        self.concurrency = concurrency;
+        self
+    }
+
+    pub fn filter(mut self, filter: impl Into<String>) -> Self
+    {
+        self.filter = Some(filter.into());
+        self
+    }
+
+    pub fn chunk_rows(mut self, chunk_rows: usize) -> Self
+    {
+        self.chunk_rows = chunk_rows;
+        self
+    }
+
+    pub fn compression(mut self, compression: ParquetCompression) -> Self
+    {
+        self.compression = compression;
+        self
+    }
+
+    pub fn options(mut self, options: QueryOptions) -> Self
+    {
+        self.options = options;
+        self
+    }
+
+    /// Lê todas as partições e as concatena em um DataFrame, na ordem das partições.
+    pub fn to_df(&self, engine: &EngineOracle) -> EngineResult<(DataFrame, PartitionedReport)>
+    {
+        let started = Instant::now();
+        let partitions = self.plan(engine)?;
+        let results = self.run_all(engine, &partitions, |partition, cancel| {
+            let sql = self.select(partition)?;
+            let df = engine.query_df(&sql, &partition.params, &self.options, Some(cancel))?;
+            Ok((df.height() as u64, df))
+        })?;
+
+        let mut df: Option<DataFrame> = None;
+        let mut timings = Vec::with_capacity(results.len());
+        for (timing, part) in results
+        {
+            match &mut df
+            {
+                Some(df) =>
+                {
+                    df.vstack_mut(&part)?;
+                },
+                None => df = Some(part),
+            }
+            timings.push(timing);
+        }
+        let mut df = df.unwrap_or_default();
+        df.align_chunks_par();
+
+        let report = self.report(timings, started);
+        Ok((df, report))
+    }
+
+    /// Grava cada partição em `<dir>/<tabela>_part<NNN>.parquet`, em blocos de `chunk_rows`.
+    ///
+    /// Partições vazias também geram arquivo, para que o conjunto fique completo.
+    pub fn to_parquet(&self, engine: &EngineOracle, dir: &Path) -> EngineResult<PartitionedReport>
+    {
+        let started = Instant::now();
+        std::fs::create_dir_all(dir).map_err(|e| EngineError::io(dir, e))?;
+
+        let partitions = self.plan(engine)?;
+        let prefix = quote_table_name(&self.table)?.replace('"', "").to_lowercase();
+        let options = ExportOptions {
+            format: ExportFormat::Parquet {
+                compression: self.compression,
+            },
+            chunk_rows: self.chunk_rows,
+            query: self.options.clone(),
+            cancel: None,
+        };
+
+        let results = self.run_all(engine, &partitions, |partition, cancel| {
+            let sql = self.select(partition)?;
+            let path = dir.join(format!("{}_part{:03}.parquet", prefix, partition.index));
+            let options = ExportOptions {
+                cancel: Some(cancel.clone()),
+                ..options.clone()
+            };
+            let rows = export_query(engine, &sql, &partition.params, &path, &options, |_| {})?;
+            Ok((rows, path))
+        })?;
+
+        let timings = results
+            .into_iter()
+            .map(|(timing, path)| PartitionTiming {
+                file: Some(path),
+                ..timing
+            })
+            .collect();
+        Ok(self.report(timings, started))
+    }
+
+    /// Calcula as partições conforme a estratégia.
+    ///
+    /// Podem sair menos partições que o pedido quando a tabela ou a faixa de valores é pequena.
+    pub fn plan(&self, engine: &EngineOracle) -> EngineResult<Vec<Partition>>
+    {
+        if self.partitions == 0 || self.concurrency == 0
+        {
+            return Err(EngineError::InvalidArgument(
+                "partitions e concurrency precisam ser maiores que zero".to_string(),
+            ));
+        }
+
+        let partitions = match &self.strategy
+        {
+            PartitionStrategy::OraHash(column) =>
+            {
+                let column = quote_identifier(column)?;
+                let buckets = self.partitions - 1;
+                (0..self.partitions)
+                    .map(|k| {
+                        // ORA_HASH de nulo também é nulo: essas linhas vão para a primeira partição
+                        let null = if k == 0
+                        {
+                            format!(" OR {} IS NULL", column)
+                        }
+                        else
+                        {
+                            String::new()
+                        };
+                        Partition {
+                            index: k,
+                            predicate: format!("(ORA_HASH({}, {}) = :1{})", column, buckets, null),
+                            params: vec![SqlValue::Int(k as i64)],
+                        }
+                    })
+                    .collect()
+            },
+            PartitionStrategy::RowidRange => ranges("ROWID", "CHARTOROWID(:{})", false, self.rowid_cuts(engine)?),
+            PartitionStrategy::ColumnRange(column) =>
+            {
+                let expression = quote_identifier(column)?;
+                let cuts = self.column_cuts(engine, &expression)?;
+                ranges(&expression, ":{}", true, cuts)
+            },
+        };
+
+        info!("{}: {} partições por {:?}.", self.table, partitions.len(), self.strategy);
+        Ok(partitions)
+    }
+
+    /// Primeiro ROWID de cada grupo de `NTILE`, exceto o do primeiro grupo.
+    fn rowid_cuts(&self, engine: &EngineOracle) -> EngineResult<Vec<SqlValue>>
+    {
+        let sql = format!(
+            "SELECT ROWIDTOCHAR(MIN(RID)) AS CUT FROM \
+             (SELECT ROWID AS RID, NTILE(:1) OVER (ORDER BY ROWID) AS BUCKET FROM {} t{}) \
+             GROUP BY BUCKET ORDER BY MIN(RID)",
+            quote_table_name(&self.table)?,
+            self.where_clause()
+        );
+        let df = engine.query_df(&sql, &[SqlValue::Int(self.partitions as i64)], &QueryOptions::default(), None)?;
+        let cuts = first_column(&df, &sql)?
+            .cast(&DataType::String)?
+            .str()?
+            .into_iter()
+            .skip(1)
+            .flatten()
+            .map(|rowid| SqlValue::Text(rowid.to_string()))
+            .collect();
+        Ok(cuts)
+    }
+
+    /// Cortes igualmente espaçados entre o mínimo e o máximo da coluna.
+    fn column_cuts(&self, engine: &EngineOracle, expression: &str) -> EngineResult<Vec<SqlValue>>
+    {
+        let sql = format!(
+            "SELECT MIN({0}) AS LO, MAX({0}) AS HI FROM {1} t{2}",
+            expression,
+            quote_table_name(&self.table)?,
+            self.where_clause()
+        );
+        let df = engine.query_df(&sql, &[], &QueryOptions::default(), None)?;
+        let (lo, hi) = match df.get_columns()
+        {
+            [lo, hi] => (lo.as_materialized_series(), hi.as_materialized_series()),
+            _ => return Err(EngineError::InvalidArgument(format!("consulta inesperada: {}", sql))),
+        };
+
+        let n = self.partitions as i128;
+        let cuts: Vec<SqlValue> = match lo.dtype()
+        {
+            DataType::Datetime(..) | DataType::Date =>
+            {
+                let millis = |s: &Series| -> EngineResult<Option<i64>> {
+                    Ok(s.cast(&DataType::Datetime(TimeUnit::Milliseconds, None))?.datetime()?.get(0))
+                };
+                let (Some(lo), Some(hi)) = (millis(lo)?, millis(hi)?)
+                else
+                {
+                    return Ok(Vec::new());
+                };
+                integer_cuts(lo, hi, n)
+                    .into_iter()
+                    .filter_map(DateTime::from_timestamp_millis)
+                    .map(|dt| SqlValue::Datetime(dt.naive_utc()))
+                    .collect()
+            },
+            dtype if dtype.is_integer() =>
+            {
+                let int = |s: &Series| -> EngineResult<Option<i64>> { Ok(s.strict_cast(&DataType::Int64)?.i64()?.get(0)) };
+                let (Some(lo), Some(hi)) = (int(lo)?, int(hi)?)
+                else
+                {
+                    return Ok(Vec::new());
+                };
+                integer_cuts(lo, hi, n).into_iter().map(SqlValue::Int).collect()
+            },
+            dtype if dtype.is_primitive_numeric() || dtype.is_decimal() =>
+            {
+                let float = |s: &Series| -> EngineResult<Option<f64>> { Ok(s.cast(&DataType::Float64)?.f64()?.get(0)) };
+                let (Some(lo), Some(hi)) = (float(lo)?, float(hi)?)
+                else
+                {
+                    return Ok(Vec::new());
+                };
+                let mut cuts: Vec<f64> = (1..self.partitions)
+                    .map(|k| lo + (hi - lo) * k as f64 / self.partitions as f64)
+                    .filter(|cut| *cut > lo)
+                    .collect();
+                cuts.dedup();
+                cuts.into_iter().map(SqlValue::Float).collect()
+            },
+            DataType::Null => Vec::new(),
+            dtype =>
+            {
+                return Err(EngineError::InvalidArgument(format!(
+                    "a coluna {} é do tipo {}; ColumnRange exige coluna numérica ou de data",
+                    expression, dtype
+                )))
+            },
+        };
+        Ok(cuts)
+    }
+
+    /// Executa `work` em cada partição com até `concurrency` threads e devolve os resultados
+    /// na ordem das partições. Na primeira falha, as partições ainda não iniciadas são
+    /// descartadas e as em andamento, canceladas: `to_df` interrompe a instrução no servidor e
+    /// `to_parquet` para no próximo bloco, removendo o arquivo parcial.
+    fn run_all<T: Send>(
+        &self,
+        engine: &EngineOracle,
+        partitions: &[Partition],
+        work: impl Fn(&Partition, &CancelToken) -> EngineResult<(u64, T)> + Sync,
+    ) -> EngineResult<Vec<(PartitionTiming, T)>>
+    {
+        if engine.pool_stats()?.is_none() && self.concurrency > 1
+        {
+            warn!("{}: engine com conexão única; as partições serão lidas em série.", self.table);
+        }
+
+        let next = AtomicUsize::new(0);
+        let cancel = CancelToken::new();
+        let results: Mutex<Vec<Option<PartitionResult<T>>>> = Mutex::new(partitions.iter().map(|_| None).collect());
+
+        thread::scope(|scope| {
+            for _ in 0..self.concurrency.min(partitions.len())
+            {
+                scope.spawn(|| {
+                    while !cancel.is_cancelled()
+                    {
+                        let Some(partition) = partitions.get(next.fetch_add(1, Ordering::Relaxed))
+                        else
+                        {
+                            break;
+                        };
+
+                        let started = Instant::now();
+                        let result = work(partition, &cancel).map(|(rows, value)| {
+                            let elapsed = started.elapsed();
+                            info!(
+                                "{}: partição {} com {} linhas em {:?}.",
+                                self.table, partition.index, rows, elapsed
+                            );
+                            let timing = PartitionTiming {
+                                index: partition.index,
+                                predicate: partition.predicate.clone(),
+                                rows,
+                                elapsed,
+                                file: None,
+                            };
+                            (timing, value)
+                        });
+                        if result.is_err()
+                        {
+                            cancel.cancel();
+                        }
+                        results.lock().unwrap_or_else(PoisonError::into_inner)[partition.index] = Some(result);
+                    }
+                });
+            }
+        });
+
+        // O erro original tem precedência sobre os cancelamentos que ele provocou
+        let results = results.into_inner().unwrap_or_else(PoisonError::into_inner);
+        let (done, errors): (Vec<_>, Vec<_>) = results.into_iter().flatten().partition(Result::is_ok);
+        let mut errors = errors.into_iter().filter_map(Result::err);
+        let first_error = match errors.next()
+        {
+            Some(EngineError::Cancelled { .. }) =>
+            {
+                errors
+                    .find(|e| !matches!(e, EngineError::Cancelled { .. }))
+                    .or(Some(EngineError::Cancelled {
+                        sql_id: None,
+                        source: None,
+                    }))
+            },
+            first => first,
+        };
+        match first_error
+        {
+            Some(e) => Err(e),
+            None => Ok(done.into_iter().filter_map(Result::ok).collect()),
+        }
+    }
+
+    fn select(&self, partition: &Partition) -> EngineResult<String>
+    {
+        let mut conditions = Vec::new();
+        if !partition.predicate.is_empty()
+        {
+            conditions.push(partition.predicate.clone());
+        }
+        if let Some(filter) = &self.filter
+        {
+            conditions.push(format!("({})", filter));
+        }
+
+        let mut sql = format!("SELECT t.* FROM {} t", quote_table_name(&self.table)?);
+        if !conditions.is_empty()
+        {
+            sql.push_str(" WHERE ");
+            sql.push_str(&conditions.join(" AND "));
+        }
+        Ok(sql)
+    }
+
+    fn where_clause(&self) -> String
+    {
+        self.filter.as_ref().map(|f| format!(" WHERE {}", f)).unwrap_or_default()
+    }
+
+    fn report(&self, partitions: Vec<PartitionTiming>, started: Instant) -> PartitionedReport
+    {
+        let rows = partitions.iter().map(|p| p.rows).sum();
+        let elapsed = started.elapsed();
+        info!(
+            "{}: {} linhas em {} partições, {:?} no total.",
+            self.table,
+            rows,
+            partitions.len(),
+            elapsed
+        );
+        PartitionedReport {
+            partitions,
+            rows,
+            elapsed,
+        }
+    }
+}
+
+/// Partições contíguas a partir dos cortes: `< c1`, `>= c1 AND < c2`, ..., `>= cN`.
+///
+/// `bind` é o formato do bind de cada corte (ex.: `"CHARTOROWID(:{})"`); com `nulls`, as linhas
+/// com a expressão nula entram na primeira partição.
+fn ranges(expression: &str, bind: &str, nulls: bool, cuts: Vec<SqlValue>) -> Vec<Partition>
+{
+    let bind = |position: usize| bind.replace("{}", &position.to_string());
+    let count = cuts.len() + 1;
+    if count == 1
+    {
+        return vec![Partition {
+            index: 0,
+            predicate: String::new(),
+            params: Vec::new(),
+        }];
+    }
+
+    (0..count)
+        .map(|i| {
+            let mut conditions = Vec::new();
+            let mut params = Vec::new();
+            if i > 0
+            {
+                params.push(cuts[i - 1].clone());
+                conditions.push(format!("{} >= {}", expression, bind(params.len())));
+            }
+            if i < count - 1
+            {
+                params.push(cuts[i].clone());
+                conditions.push(format!("{} < {}", expression, bind(params.len())));
+            }
+
+            let mut predicate = conditions.join(" AND ");
+            if nulls && i == 0
+            {
+                predicate = format!("({} OR {} IS NULL)", predicate, expression);
+            }
+            Partition {
+                index: i,
+                predicate,
+                params,
+            }
+        })
+        .collect()
+}
+
+/// Até `n - 1` cortes inteiros distintos em `(lo, hi]`.
+fn integer_cuts(lo: i64, hi: i64, n: i128) -> Vec<i64>
+{
+    let mut cuts: Vec<i64> = (1..n)
+        .map(|k| (lo as i128 + (hi as i128 - lo as i128) * k / n) as i64)
+        .filter(|cut| *cut > lo)
+        .collect();
+    cuts.dedup();
+    cuts
+}
+
+fn first_column<'a>(df: &'a DataFrame, sql: &str) -> EngineResult<&'a Series>
+{
+    df.get_columns()
+        .first()
+        .map(|c| c.as_materialized_series())
+        .ok_or_else(|| EngineError::InvalidArgument(format!("consulta sem colunas: {}", sql)))
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn integer_cuts_are_distinct_and_inside_the_range()
+    {
+        assert_eq!(integer_cuts(0, 10, 4), vec![2, 5, 7]);
+        assert_eq!(integer_cuts(1, 100, 1), Vec::<i64>::new());
+        assert_eq!(integer_cuts(5, 5, 4), Vec::<i64>::new());
+        // Menos valores que partições: cortes repetidos somem
+        assert_eq!(integer_cuts(0, 2, 5), vec![1]);
+        // Sem estouro nos extremos do i64
+        assert_eq!(integer_cuts(i64::MIN, i64::MAX, 2), vec![-1]);
+    }
+
+    #[test]
+    fn ranges_cover_everything_between_cuts()
+    {
+        let partitions = ranges("t.ID", ":{}", true, vec![SqlValue::Int(10), SqlValue::Int(20)]);
+        assert_eq!(
+            partitions,
+            vec![
+                Partition {
+                    index: 0,
+                    predicate: "(t.ID < :1 OR t.ID IS NULL)".to_string(),
+                    params: vec![SqlValue::Int(10)],
+                },
+                Partition {
+                    index: 1,
+                    predicate: "t.ID >= :1 AND t.ID < :2".to_string(),
+                    params: vec![SqlValue::Int(10), SqlValue::Int(20)],
+                },
+                Partition {
+                    index: 2,
+                    predicate: "t.ID >= :1".to_string(),
+                    params: vec![SqlValue::Int(20)],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ranges_without_cuts_is_a_single_partition()
+    {
+        let partitions = ranges("ROWID", "CHARTOROWID(:{})", false, Vec::new());
+        assert_eq!(partitions.len(), 1);
+        assert!(partitions[0].predicate.is_empty());
+        assert!(partitions[0].params.is_empty());
+
+        let partitions = ranges("ROWID", "CHARTOROWID(:{})", false, vec![SqlValue::Text("AAA".to_string())]);
+        assert_eq!(partitions[0].predicate, "ROWID < CHARTOROWID(:1)");
+        assert_eq!(partitions[1].predicate, "ROWID >= CHARTOROWID(:1)");
+    }
+}
@@ -0,0 +1,249 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+use log::info;
+use log::warn;
+use oracle::oci_attr::TransactionInProgress;
+use oracle::sql_type::ToSql;
+use oracle::Connection;
+use polars::prelude::*;
+use serde::Deserialize;
+
+use super::connection::EngineOracle;
+use super::error::sql_id;
+use super::error::EngineError;
+use super::error::EngineResult;
+use super::options::QueryOptions;
+use super::stream::fetch_chunk;
+use super::stream::open_result_set;
+use super::stream::result_schema;
+
+/// Passos do plano na ordem de `ID`, com a operação indentada pela profundidade, como no
+/// `DBMS_XPLAN`. `TIME_SECS` é o tempo estimado pelo otimizador.
+const PLAN_STEPS_SQL: &str = "
+    SELECT
+        CAST(ID AS NUMBER(10)) AS ID,
+        CAST(PARENT_ID AS NUMBER(10)) AS PARENT_ID,
+        LPAD(' ', 2 * DEPTH) || OPERATION || NVL2(OPTIONS, ' ' || OPTIONS, NULL) AS OPERATION,
+        NVL2(OBJECT_OWNER, OBJECT_OWNER || '.', NULL) || OBJECT_NAME AS OBJECT_NAME,
+        CAST(COST AS NUMBER(18)) AS COST,
+        CAST(CARDINALITY AS NUMBER(18)) AS CARDINALITY,
+        CAST(BYTES AS NUMBER(18)) AS BYTES,
+        CAST(TIME AS NUMBER(18)) AS TIME_SECS,
+        ACCESS_PREDICATES,
+        FILTER_PREDICATES
+    FROM PLAN_TABLE
+    WHERE STATEMENT_ID = :1
+    ORDER BY ID
+";
+
+const PLAN_TEXT_SQL: &str = "SELECT PLAN_TABLE_OUTPUT FROM TABLE(DBMS_XPLAN.DISPLAY('PLAN_TABLE', :1, 'TYPICAL'))";
+
+const ROOT_COST_SQL: &str = "SELECT CAST(COST AS NUMBER(18)) FROM PLAN_TABLE WHERE STATEMENT_ID = :1 AND ID = 0";
+
+/// Savepoint que delimita as linhas do EXPLAIN PLAN dentro de uma transação em andamento.
+const EXPLAIN_SAVEPOINT: &str = "RC_EXPLAIN_PLAN";
+
+/// Sequência que torna único o `STATEMENT_ID` de cada EXPLAIN PLAN entre threads.
+static EXPLAIN_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// O que fazer quando o custo estimado passa de `CostLimit::max_cost`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CostAction
+{
+    /// Registra um aviso no log e executa a consulta.
+    #[default]
+    Warn,
+    /// Recusa a consulta com `EngineError::CostExceeded`.
+    Refuse,
+}
+
+impl FromStr for CostAction
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        match s.trim().to_ascii_lowercase().as_str()
+        {
+            "warn" => Ok(CostAction::Warn),
+            "refuse" => Ok(CostAction::Refuse),
+            other => Err(format!("ação desconhecida: {:?} (use warn ou refuse)", other)),
+        }
+    }
+}
+
+impl fmt::Display for CostAction
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            CostAction::Warn => write!(f, "warn"),
+            CostAction::Refuse => write!(f, "refuse"),
+        }
+    }
+}
+
+/// Limite de custo do otimizador conferido com EXPLAIN PLAN antes de cada consulta.
+///
+/// A conferência custa um parse a mais por consulta; use em endpoints em que o SQL varia
+/// (filtros vindos da API), não em extrações fixas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CostLimit
+{
+    /// Custo máximo do passo raiz do plano (coluna `COST` do `DBMS_XPLAN`).
+    pub max_cost: u64,
+    pub action: CostAction,
+}
+
+impl CostLimit
+{
+    pub fn warn(max_cost: u64) -> Self
+    {
+        Self {
+            max_cost,
+            action: CostAction::Warn,
+        }
+    }
+
+    pub fn refuse(max_cost: u64) -> Self
+    {
+        Self {
+            max_cost,
+            action: CostAction::Refuse,
+        }
+    }
+}
+
+/// Custo total do plano (o do passo `ID = 0`) em um DataFrame de `EngineOracle::explain`.
+pub fn plan_cost(steps: &DataFrame) -> Option<u64>
+{
+    let ids = steps.column("ID").ok()?.i64().ok()?;
+    let costs = steps.column("COST").ok()?.i64().ok()?;
+    ids.into_iter()
+        .zip(costs)
+        .find(|(id, _)| *id == Some(0))
+        .and_then(|(_, cost)| cost)
+        .map(|cost| cost.max(0) as u64)
+}
+
+/// Executa EXPLAIN PLAN para `sql` e entrega o `STATEMENT_ID` a `read`.
+///
+/// As linhas gravadas na PLAN_TABLE são descartadas ao final. Sem transação aberta, com
+/// rollback (a sessão volta a aceitar `SET TRANSACTION`); com uma transação em andamento,
+/// só até um savepoint criado antes do EXPLAIN, preservando o que ela já fez.
+fn with_plan<T>(
+    conn: &Connection,
+    sql: &str,
+    params: &[&dyn ToSql],
+    read: impl FnOnce(&str) -> EngineResult<T>,
+) -> EngineResult<T>
+{
+    let statement_id = format!("rc{}_{}", sql_id(sql), EXPLAIN_SEQ.fetch_add(1, Ordering::Relaxed));
+    let explain = format!(
+        "EXPLAIN PLAN SET STATEMENT_ID = '{}' FOR {}",
+        statement_id,
+        sql.trim().trim_end_matches(';')
+    );
+
+    let in_transaction = conn.oci_attr::<TransactionInProgress>()?;
+    if in_transaction
+    {
+        conn.execute(&format!("SAVEPOINT {}", EXPLAIN_SAVEPOINT), &[])?;
+    }
+    let result = conn
+        .execute(&explain, params)
+        .map_err(EngineError::from)
+        .and_then(|_| read(&statement_id));
+    if in_transaction
+    {
+        conn.execute(&format!("ROLLBACK TO SAVEPOINT {}", EXPLAIN_SAVEPOINT), &[])?;
+    }
+    else
+    {
+        conn.rollback()?;
+    }
+    result.map_err(|e| e.with_sql(sql))
+}
+
+/// Passos do plano de `sql` como DataFrame.
+pub(crate) fn explain_on(conn: &Connection, sql: &str, params: &[&dyn ToSql]) -> EngineResult<DataFrame>
+{
+    with_plan(conn, sql, params, |statement_id| {
+        let options = QueryOptions::default();
+        let mut rows = open_result_set(conn, PLAN_STEPS_SQL, &[&statement_id], &options)?;
+        let schema = result_schema(&rows, &options);
+        fetch_chunk(&mut rows, &schema, None, 0, None)
+    })
+}
+
+/// Confere o custo estimado de `sql` contra o limite: avisa no log ou recusa a consulta.
+pub(crate) fn enforce_cost_limit(conn: &Connection, sql: &str, params: &[&dyn ToSql], limit: &CostLimit)
+    -> EngineResult<()>
+{
+    let cost = with_plan(conn, sql, params, |statement_id| {
+        Ok(conn.query_row_as::<Option<i64>>(ROOT_COST_SQL, &[&statement_id])?)
+    })?;
+    let Some(cost) = cost.map(|cost| cost.max(0) as u64)
+    else
+    {
+        return Ok(());
+    };
+    if cost <= limit.max_cost
+    {
+        return Ok(());
+    }
+
+    match limit.action
+    {
+        CostAction::Warn =>
+        {
+            warn!(
+                "Custo estimado {} acima do limite {} (sql_id {}); executando mesmo assim.",
+                cost,
+                limit.max_cost,
+                sql_id(sql)
+            );
+            Ok(())
+        },
+        CostAction::Refuse => Err(EngineError::CostExceeded {
+            cost,
+            limit: limit.max_cost,
+            sql_id: Some(sql_id(sql)),
+        }),
+    }
+}
+
+impl EngineOracle
+{
+    /// Roda EXPLAIN PLAN para a consulta e devolve um passo do plano por linha: `ID`,
+    /// `PARENT_ID`, `OPERATION`, `OBJECT_NAME`, `COST`, `CARDINALITY`, `BYTES`, `TIME_SECS`,
+    /// `ACCESS_PREDICATES` e `FILTER_PREDICATES`. Use `plan_cost` para o custo total.
+    ///
+    /// A consulta não é executada. Os binds são aceitos, mas o otimizador não olha seus valores
+    /// no EXPLAIN PLAN, então o plano real pode diferir quando há histogramas.
+    pub fn explain(&self, sql: &str, params: &[&dyn ToSql]) -> EngineResult<DataFrame>
+    {
+        self.check_read(sql).map_err(|e| e.with_sql(sql))?;
+        let conn = self.connection()?;
+        let steps = explain_on(&conn, sql, params)?;
+        info!("Plano obtido; {} passos, custo {:?}.", steps.height(), plan_cost(&steps));
+        Ok(steps)
+    }
+
+    /// Plano formatado pelo `DBMS_XPLAN.DISPLAY`, uma linha por item, como no SQL Developer.
+    pub fn explain_text(&self, sql: &str, params: &[&dyn ToSql]) -> EngineResult<Vec<String>>
+    {
+        self.check_read(sql).map_err(|e| e.with_sql(sql))?;
+        let conn = self.connection()?;
+        with_plan(&conn, sql, params, |statement_id| {
+            conn.query_as::<Option<String>>(PLAN_TEXT_SQL, &[&statement_id])?
+                .map(|line| Ok(line?.unwrap_or_default()))
+                .collect()
+        })
+    }
+}
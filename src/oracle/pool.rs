@@ -0,0 +1,179 @@
+use std::ops::Deref;
+use std::sync::Arc;
+use std::sync::Condvar;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::thread;
+use std::thread::ThreadId;
+use std::time::Duration;
+
+use oracle::pool::GetMode;
+use oracle::pool::Pool;
+use oracle::pool::PoolBuilder;
+use oracle::Connection;
+use serde::Serialize;
+
+use super::error::EngineError;
+use super::error::EngineResult;
+
+/// Parâmetros do pool de conexões do `EngineOracle`.
+#[derive(Debug, Clone)]
+pub struct PoolConfig
+{
+    pub min_connections: u32,
+    pub max_connections: u32,
+    pub connection_increment: u32,
+    /// Conexões ociosas por mais tempo que isso são encerradas (`Duration::ZERO` desativa).
+    /// O driver trabalha em segundos inteiros; frações são arredondadas para cima.
+    pub idle_timeout: Duration,
+    /// Conexões ociosas há mais tempo que isso recebem um ping ao serem retiradas do pool, em
+    /// segundos inteiros como `idle_timeout`.
+    /// `Some(Duration::ZERO)` valida toda retirada; `None` desativa a validação.
+    pub ping_interval: Option<Duration>,
+    /// Tempo máximo de espera por uma conexão livre quando o pool está saturado.
+    pub checkout_timeout: Duration,
+}
+
+impl Default for PoolConfig
+{
+    fn default() -> Self
+    {
+        Self {
+            min_connections: 1,
+            max_connections: 10,
+            connection_increment: 1,
+            idle_timeout: Duration::from_secs(300),
+            ping_interval: Some(Duration::from_secs(60)),
+            checkout_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl PoolConfig
+{
+    /// Cria o pool no Oracle com estes parâmetros.
+    pub(crate) fn build(&self, username: &str, password: &str, connect_string: &str) -> EngineResult<Pool>
+    {
+        let mut builder = PoolBuilder::new(username, password, connect_string);
+        builder
+            .min_connections(self.min_connections)
+            .max_connections(self.max_connections)
+            .connection_increment(self.connection_increment)
+            .get_mode(GetMode::TimedWait(self.checkout_timeout));
+        builder.timeout(whole_secs(self.idle_timeout)).map_err(EngineError::connect)?;
+        builder
+            .ping_interval(self.ping_interval.map(whole_secs))
+            .map_err(EngineError::connect)?;
+        builder.build().map_err(EngineError::connect)
+    }
+}
+
+/// Arredonda para cima até o segundo inteiro: o driver trunca, e 500 ms virariam 0, que para ele
+/// significa "nunca encerrar" no `idle_timeout`.
+pub(crate) fn whole_secs(duration: Duration) -> Duration
+{
+    Duration::from_secs(duration.as_secs() + u64::from(duration.subsec_nanos() > 0))
+}
+
+/// Retrato do uso do pool, exposto para monitorar saturação.
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolStats
+{
+    pub open: u32,
+    pub busy: u32,
+    pub min: u32,
+    pub max: u32,
+}
+
+impl PoolStats
+{
+    /// Fração das conexões máximas que está em uso (0.0 a 1.0).
+    pub fn saturation(&self) -> f64
+    {
+        if self.max == 0
+        {
+            0.0
+        }
+        else
+        {
+            self.busy as f64 / self.max as f64
+        }
+    }
+}
+
+/// Conexão emprestada pelo `EngineOracle`: a conexão única ou uma conexão retirada do pool,
+/// que volta ao pool quando é descartada.
+///
+/// A conexão única é compartilhada por `Arc`, então continua válida mesmo que o engine
+/// reconecte enquanto ela estiver em uso. Ela vem com o `SessionGuard`, que dá uso exclusivo
+/// da sessão até o `EngineConnection` ser descartado.
+pub enum EngineConnection
+{
+    Single(Arc<Connection>, SessionGuard),
+    Pooled(Connection),
+}
+
+/// Uso exclusivo da sessão da conexão única.
+///
+/// Commit, rollback, call timeout e `break_execution` valem para a sessão inteira; sem a
+/// exclusividade, uma chamada desfaria a transação ou interromperia a instrução de outra.
+#[derive(Debug, Default)]
+pub(crate) struct SessionLock
+{
+    owner: Mutex<Option<ThreadId>>,
+    released: Condvar,
+}
+
+impl SessionLock
+{
+    /// Espera a sessão ficar livre e a reserva para a thread atual.
+    ///
+    /// Falha, em vez de travar para sempre, quando a própria thread já tem a sessão (uma
+    /// transação ou um stream ainda aberto).
+    pub(crate) fn acquire(self: &Arc<Self>) -> EngineResult<SessionGuard>
+    {
+        let me = thread::current().id();
+        let mut owner = self.owner.lock().unwrap_or_else(PoisonError::into_inner);
+        while let Some(current) = *owner
+        {
+            if current == me
+            {
+                return Err(EngineError::InvalidArgument(
+                    "a conexão única já está em uso nesta thread (transação ou stream aberto); \
+                     use a própria transação, descarte o stream antes ou configure o pool"
+                        .to_string(),
+                ));
+            }
+            owner = self.released.wait(owner).unwrap_or_else(PoisonError::into_inner);
+        }
+        *owner = Some(me);
+        Ok(SessionGuard(Arc::clone(self)))
+    }
+}
+
+/// Reserva da sessão da conexão única, liberada ao ser descartada.
+#[derive(Debug)]
+pub struct SessionGuard(Arc<SessionLock>);
+
+impl Drop for SessionGuard
+{
+    fn drop(&mut self)
+    {
+        *self.0.owner.lock().unwrap_or_else(PoisonError::into_inner) = None;
+        self.0.released.notify_one();
+    }
+}
+
+impl Deref for EngineConnection
+{
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection
+    {
+        match self
+        {
+            EngineConnection::Single(conn, _) => conn,
+            EngineConnection::Pooled(conn) => conn,
+        }
+    }
+}
@@ -0,0 +1,385 @@
+use std::collections::HashMap;
+
+use log::info;
+use oracle::sql_type::OracleType;
+use oracle::sql_type::RefCursor;
+use oracle::sql_type::ToSql;
+use oracle::Connection;
+use polars::prelude::DataFrame;
+
+use super::cancel::run_guarded;
+use super::cancel::CancelToken;
+use super::connection::oracle_value;
+use super::connection::EngineOracle;
+use super::engine::SqlValue;
+use super::error::EngineResult;
+use super::options::QueryOptions;
+use super::sql::quote_identifier;
+use super::sql::quote_routine_name;
+use super::stream::cursor_schema;
+use super::stream::fetch_chunk;
+use super::transaction::Transaction;
+
+/// Chave do valor de retorno de uma função em `ProcedureResult`.
+pub const RETURN_KEY: &str = "return";
+
+/// Tipo de um parâmetro OUT (ou IN OUT) ou do retorno de uma função.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutType
+{
+    /// `NUMBER` inteiro, lido como `SqlValue::Int`.
+    Int,
+    /// `NUMBER`/`BINARY_DOUBLE`, lido como `SqlValue::Float`.
+    Float,
+    /// `VARCHAR2` com o tamanho máximo informado (até 32767 em PL/SQL).
+    Text(u32),
+    /// `CLOB`, lido inteiro como `SqlValue::Text`.
+    Clob,
+    /// `DATE`, lido como `SqlValue::Date` (o horário é descartado).
+    Date,
+    /// `DATE`/`TIMESTAMP`, lido como `SqlValue::Datetime`.
+    Datetime,
+    /// `SYS_REFCURSOR`, convertido em DataFrame.
+    Cursor,
+}
+
+impl OutType
+{
+    fn oracle_type(self) -> OracleType
+    {
+        match self
+        {
+            OutType::Int => OracleType::Int64,
+            OutType::Float => OracleType::BinaryDouble,
+            OutType::Text(size) => OracleType::Varchar2(size),
+            OutType::Clob => OracleType::CLOB,
+            OutType::Date | OutType::Datetime => OracleType::Timestamp(6),
+            OutType::Cursor => OracleType::RefCursor,
+        }
+    }
+}
+
+/// Direção de um parâmetro da rotina.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProcParam
+{
+    In(SqlValue),
+    Out(OutType),
+    InOut(SqlValue, OutType),
+}
+
+/// Chamada de procedure ou função PL/SQL, com os parâmetros passados por nome.
+///
+/// ```ignore
+/// let call = ProcedureCall::procedure("SYSADM.PKG_CHASSI.LISTAR")
+///     .input("P_PLANTA", "BR01")
+///     .output("P_TOTAL", OutType::Int)
+///     .cursor("P_ROWS");
+/// let result = engine.call_procedure(&call, &QueryOptions::default())?;
+/// let df = result.cursor("P_ROWS");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProcedureCall
+{
+    name: String,
+    returns: Option<OutType>,
+    params: Vec<(String, ProcParam)>,
+}
+
+impl ProcedureCall
+{
+    pub fn procedure(name: impl Into<String>) -> Self
+    {
+        Self {
+            name: name.into(),
+            returns: None,
+            params: Vec::new(),
+        }
+    }
+
+    /// Função cujo retorno fica em `ProcedureResult` sob a chave `RETURN_KEY`.
+    pub fn function(name: impl Into<String>, returns: OutType) -> Self
+    {
+        Self {
+            name: name.into(),
+            returns: Some(returns),
+            params: Vec::new(),
+        }
+    }
+
+    pub fn input(mut self, name: impl Into<String>, value: impl Into<SqlValue>) -> Self
+    {
+        self.params.push((name.into(), ProcParam::In(value.into())));
+        self
+    }
+
+    pub fn output(mut self, name: impl Into<String>, kind: OutType) -> Self
+    {
+        self.params.push((name.into(), ProcParam::Out(kind)));
+        self
+    }
+
+    pub fn in_out(mut self, name: impl Into<String>, value: impl Into<SqlValue>, kind: OutType) -> Self
+    {
+        self.params.push((name.into(), ProcParam::InOut(value.into(), kind)));
+        self
+    }
+
+    /// Parâmetro OUT `SYS_REFCURSOR`.
+    pub fn cursor(self, name: impl Into<String>) -> Self
+    {
+        self.output(name, OutType::Cursor)
+    }
+
+    /// Bloco PL/SQL da chamada, com binds posicionais: o retorno (se houver) e depois os
+    /// parâmetros na ordem em que foram adicionados.
+    pub fn sql(&self) -> EngineResult<String>
+    {
+        let args = self
+            .params
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| {
+                let position = i + 1 + usize::from(self.returns.is_some());
+                Ok(format!("{} => :{}", quote_identifier(name)?, position))
+            })
+            .collect::<EngineResult<Vec<String>>>()?;
+
+        let call = format!("{}({})", quote_routine_name(&self.name)?, args.join(", "));
+        Ok(match self.returns
+        {
+            Some(_) => format!("BEGIN :1 := {}; END;", call),
+            None => format!("BEGIN {}; END;", call),
+        })
+    }
+
+    /// Binds na ordem do SQL, com o tipo de saída e a chave no resultado de cada um.
+    fn binds(&self) -> Vec<(Option<&str>, Option<&SqlValue>, Option<OutType>)>
+    {
+        let returns = self.returns.map(|kind| (Some(RETURN_KEY), None, Some(kind)));
+        let params = self.params.iter().map(|(name, param)| match param
+        {
+            ProcParam::In(value) => (None, Some(value), None),
+            ProcParam::Out(kind) => (Some(name.as_str()), None, Some(*kind)),
+            ProcParam::InOut(value, kind) => (Some(name.as_str()), Some(value), Some(*kind)),
+        });
+        returns.into_iter().chain(params).collect()
+    }
+}
+
+/// Valores devolvidos pela rotina: escalares tipados e um DataFrame por REF CURSOR.
+#[derive(Debug, Clone, Default)]
+pub struct ProcedureResult
+{
+    pub values: HashMap<String, SqlValue>,
+    pub cursors: HashMap<String, DataFrame>,
+}
+
+impl ProcedureResult
+{
+    /// Valor escalar OUT (ou o retorno da função, em `RETURN_KEY`).
+    pub fn value(&self, name: &str) -> Option<&SqlValue>
+    {
+        self.values.get(name)
+    }
+
+    /// DataFrame do REF CURSOR devolvido no parâmetro (ou no retorno da função).
+    pub fn cursor(&self, name: &str) -> Option<&DataFrame>
+    {
+        self.cursors.get(name)
+    }
+}
+
+/// Bind IN OUT: o valor de entrada com o tipo Oracle da saída, para que o driver reserve
+/// espaço suficiente para o que a rotina devolver.
+struct InOutBind<'a>
+{
+    value: &'a dyn ToSql,
+    oracle_type: OracleType,
+}
+
+impl ToSql for InOutBind<'_>
+{
+    fn oratype(&self, _conn: &Connection) -> oracle::Result<OracleType>
+    {
+        Ok(self.oracle_type.clone())
+    }
+
+    fn to_sql(&self, val: &mut oracle::SqlValue) -> oracle::Result<()>
+    {
+        self.value.to_sql(val)
+    }
+}
+
+/// Executa a chamada na conexão e lê as saídas. Não faz commit.
+pub(crate) fn call_routine(
+    conn: &Connection,
+    call: &ProcedureCall,
+    options: &QueryOptions,
+    cancel: Option<&CancelToken>,
+) -> EngineResult<ProcedureResult>
+{
+    let sql = call.sql()?;
+    let run = || -> EngineResult<ProcedureResult> {
+        let binds = call.binds();
+        let oracle_types: Vec<Option<OracleType>> =
+            binds.iter().map(|(_, _, kind)| kind.map(OutType::oracle_type)).collect();
+
+        let in_out: Vec<Option<InOutBind<'_>>> = binds
+            .iter()
+            .zip(&oracle_types)
+            .map(|((_, value, _), oracle_type)| match (value, oracle_type)
+            {
+                (Some(value), Some(oracle_type)) => Some(InOutBind {
+                    value: oracle_value(value),
+                    oracle_type: oracle_type.clone(),
+                }),
+                _ => None,
+            })
+            .collect();
+
+        let params: Vec<&dyn ToSql> = binds
+            .iter()
+            .zip(&oracle_types)
+            .zip(&in_out)
+            .map(|(((_, value, _), oracle_type), in_out)| match (in_out, value, oracle_type)
+            {
+                (Some(bind), _, _) => bind as &dyn ToSql,
+                (None, Some(value), _) => oracle_value(value),
+                // OUT puro: o próprio OracleType é um bind nulo daquele tipo
+                (None, None, Some(oracle_type)) => oracle_type as &dyn ToSql,
+                (None, None, None) => &None::<String> as &dyn ToSql,
+            })
+            .collect();
+
+        let mut stmt = conn.statement(&sql).build()?;
+        stmt.execute(&params)?;
+
+        let mut result = ProcedureResult::default();
+        for (position, (key, _, kind)) in binds.iter().enumerate()
+        {
+            let (Some(key), Some(kind)) = (key, kind)
+            else
+            {
+                continue;
+            };
+            let idx = position + 1;
+            let value = match kind
+            {
+                OutType::Cursor =>
+                {
+                    if let Some(mut cursor) = stmt.bind_value::<_, Option<RefCursor>>(idx)?
+                    {
+                        let mut rows = cursor.query()?;
+                        let schema = cursor_schema(&rows, options);
+                        let df = fetch_chunk(&mut rows, &schema, None, 0, cancel)?;
+                        info!("Cursor {} lido; {} linhas.", key, df.height());
+                        result.cursors.insert(key.to_string(), df);
+                    }
+                    continue;
+                },
+                OutType::Int => stmt.bind_value::<_, Option<i64>>(idx)?.map(SqlValue::Int),
+                OutType::Float => stmt.bind_value::<_, Option<f64>>(idx)?.map(SqlValue::Float),
+                OutType::Text(_) | OutType::Clob => stmt.bind_value::<_, Option<String>>(idx)?.map(SqlValue::Text),
+                OutType::Date => stmt
+                    .bind_value::<_, Option<chrono::NaiveDateTime>>(idx)?
+                    .map(|dt| SqlValue::Date(dt.date())),
+                OutType::Datetime => stmt
+                    .bind_value::<_, Option<chrono::NaiveDateTime>>(idx)?
+                    .map(SqlValue::Datetime),
+            };
+            result.values.insert(key.to_string(), value.unwrap_or(SqlValue::Null));
+        }
+
+        info!("Rotina {} executada.", call.name);
+        Ok(result)
+    };
+    run().map_err(|e| e.with_sql(&sql))
+}
+
+impl EngineOracle
+{
+    /// Chama a procedure ou função, com commit ao final e rollback em caso de erro.
+    ///
+    /// Cada REF CURSOR devolvido é lido inteiro e convertido em DataFrame com as mesmas regras
+    /// de `query_to_polars_df_with` (`options`); os demais OUT vêm tipados em `values`.
+    /// Respeita o timeout das opções (ou o padrão do engine), mas não repete a chamada após
+    /// perda de conexão, já que a rotina pode ter efeitos.
+    pub fn call_procedure(&self, call: &ProcedureCall, options: &QueryOptions) -> EngineResult<ProcedureResult>
+    {
+        self.check_write("call_procedure")?;
+        let conn = self.connection_with(&options.session)?;
+        let result = run_guarded(&conn, None, self.timeout_for(options), |token| {
+            call_routine(&conn, call, options, Some(token))
+        });
+        match result
+        {
+            Ok(result) =>
+            {
+                conn.commit()?;
+                Ok(result)
+            },
+            Err(e) =>
+            {
+                conn.rollback()?;
+                Err(e)
+            },
+        }
+    }
+}
+
+impl Transaction
+{
+    /// Chama a procedure ou função como parte da transação, sem commit.
+    pub fn call_procedure(&self, call: &ProcedureCall, options: &QueryOptions) -> EngineResult<ProcedureResult>
+    {
+        call_routine(self.connection(), call, options, None)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::oracle::error::EngineError;
+
+    #[test]
+    fn procedure_binds_follow_parameter_order()
+    {
+        let call = ProcedureCall::procedure("sysadm.pkg_chassi.listar")
+            .input("p_planta", "BR01")
+            .output("P_TOTAL", OutType::Int)
+            .in_out("P_Status", "A", OutType::Text(10))
+            .cursor("P_ROWS");
+        assert_eq!(
+            call.sql().unwrap(),
+            "BEGIN SYSADM.PKG_CHASSI.LISTAR(P_PLANTA => :1, P_TOTAL => :2, P_STATUS => :3, P_ROWS => :4); END;"
+        );
+
+        let binds = call.binds();
+        assert_eq!(binds.len(), 4);
+        assert_eq!(binds[0], (None, Some(&SqlValue::Text("BR01".to_string())), None));
+        assert_eq!(binds[1], (Some("P_TOTAL"), None, Some(OutType::Int)));
+        assert_eq!(binds[3], (Some("P_ROWS"), None, Some(OutType::Cursor)));
+    }
+
+    #[test]
+    fn function_return_is_the_first_bind()
+    {
+        let call = ProcedureCall::function("calcula_frete", OutType::Float).input("P_PESO", 12.5);
+        assert_eq!(call.sql().unwrap(), "BEGIN :1 := CALCULA_FRETE(P_PESO => :2); END;");
+        assert_eq!(call.binds()[0], (Some(RETURN_KEY), None, Some(OutType::Float)));
+
+        assert_eq!(ProcedureCall::procedure("limpa").sql().unwrap(), "BEGIN LIMPA(); END;");
+    }
+
+    #[test]
+    fn rejects_invalid_names()
+    {
+        let too_deep = ProcedureCall::procedure("a.b.c.d");
+        assert!(matches!(too_deep.sql(), Err(EngineError::InvalidArgument(_))));
+
+        let bad_param = ProcedureCall::procedure("limpa").input("P\"X", 1i64);
+        assert!(matches!(bad_param.sql(), Err(EngineError::InvalidArgument(_))));
+    }
+}
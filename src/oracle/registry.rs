@@ -0,0 +1,713 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+use std::sync::RwLockReadGuard;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use log::info;
+use log::warn;
+use polars::prelude::DataFrame;
+
+use super::engine::SqlEngine;
+use super::engine::SqlValue;
+use super::error::EngineError;
+use super::error::EngineResult;
+use super::guard::bind_names;
+use super::options::QueryOptions;
+use super::types::parse_bool;
+
+/// Tipo declarado de um parâmetro de consulta nomeada.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamType
+{
+    Int,
+    Float,
+    Text,
+    Bool,
+    Date,
+    Datetime,
+}
+
+impl ParamType
+{
+    fn parse(text: &str) -> Option<Self>
+    {
+        match text
+        {
+            "int" => Some(ParamType::Int),
+            "float" => Some(ParamType::Float),
+            "text" => Some(ParamType::Text),
+            "bool" => Some(ParamType::Bool),
+            "date" => Some(ParamType::Date),
+            "datetime" => Some(ParamType::Datetime),
+            _ => None,
+        }
+    }
+
+    /// Converte o valor recebido para o tipo declarado.
+    ///
+    /// Texto é aceito para qualquer tipo (valores vindos de query string ou linha de comando);
+    /// `Null` é sempre aceito.
+    pub fn coerce(self, value: SqlValue) -> Result<SqlValue, String>
+    {
+        let mismatch = |value: &SqlValue| format!("esperado {}, recebido {:?}", self, value);
+        match (self, value)
+        {
+            (_, SqlValue::Null) => Ok(SqlValue::Null),
+            (ParamType::Int, value @ SqlValue::Int(_)) => Ok(value),
+            (ParamType::Int, SqlValue::Text(text)) => text
+                .trim()
+                .parse()
+                .map(SqlValue::Int)
+                .map_err(|_| format!("{:?} não é um inteiro", text)),
+            (ParamType::Float, SqlValue::Int(v)) => Ok(SqlValue::Float(v as f64)),
+            (ParamType::Float, value @ SqlValue::Float(_)) => Ok(value),
+            (ParamType::Float, SqlValue::Text(text)) => text
+                .trim()
+                .parse()
+                .map(SqlValue::Float)
+                .map_err(|_| format!("{:?} não é um número", text)),
+            (ParamType::Text, value @ SqlValue::Text(_)) => Ok(value),
+            (ParamType::Bool, value @ SqlValue::Bool(_)) => Ok(value),
+            (ParamType::Bool, SqlValue::Int(v @ (0 | 1))) => Ok(SqlValue::Bool(v == 1)),
+            (ParamType::Bool, SqlValue::Text(text)) => parse_bool(&text).map(SqlValue::Bool).map_err(|e| e.to_string()),
+            (ParamType::Date, value @ SqlValue::Date(_)) => Ok(value),
+            (ParamType::Date, SqlValue::Text(text)) => NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d")
+                .map(SqlValue::Date)
+                .map_err(|_| format!("{:?} não é uma data AAAA-MM-DD", text)),
+            (ParamType::Datetime, value @ SqlValue::Datetime(_)) => Ok(value),
+            (ParamType::Datetime, SqlValue::Date(date)) => Ok(SqlValue::Datetime(date.and_time(Default::default()))),
+            (ParamType::Datetime, SqlValue::Text(text)) =>
+            {
+                let text = text.trim();
+                NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S")
+                    .or_else(|_| NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S"))
+                    .or_else(|_| NaiveDate::parse_from_str(text, "%Y-%m-%d").map(|d| d.and_time(Default::default())))
+                    .map(SqlValue::Datetime)
+                    .map_err(|_| format!("{:?} não é uma data/hora AAAA-MM-DD HH:MM:SS", text))
+            },
+            (_, value) => Err(mismatch(&value)),
+        }
+    }
+}
+
+impl fmt::Display for ParamType
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let name = match self
+        {
+            ParamType::Int => "int",
+            ParamType::Float => "float",
+            ParamType::Text => "text",
+            ParamType::Bool => "bool",
+            ParamType::Date => "date",
+            ParamType::Datetime => "datetime",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Parâmetro declarado no cabeçalho (`-- param: nome tipo [= padrão]`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParamSpec
+{
+    pub name: String,
+    pub kind: ParamType,
+    /// Valor usado quando o parâmetro não é informado; sem ele, o parâmetro é obrigatório.
+    pub default: Option<SqlValue>,
+}
+
+/// Consulta carregada de um arquivo `.sql` do registro.
+#[derive(Debug, Clone)]
+pub struct NamedQuery
+{
+    pub name: String,
+    pub description: Option<String>,
+    /// Parâmetros na ordem dos binds posicionais: o primeiro é `:1`, o segundo `:2`, ...
+    pub params: Vec<ParamSpec>,
+    pub cache_ttl: Option<Duration>,
+    pub sql: String,
+    pub path: PathBuf,
+}
+
+impl NamedQuery
+{
+    /// Lê uma consulta no formato do registro:
+    ///
+    /// ```sql
+    /// -- name: chassi_loc
+    /// -- description: Localização dos chassis
+    /// -- param: limite int = 1000
+    /// -- cache_ttl: 60
+    /// SELECT * FROM SYSADM.PS_MMC_CHASSI_LOC WHERE ROWNUM <= :1
+    /// ```
+    ///
+    /// O cabeçalho são as linhas `--` iniciais; `name` assume o nome do arquivo quando omitido
+    /// e `cache_ttl` é dado em segundos. Comentários livres no cabeçalho são ignorados, inclusive
+    /// os no formato `-- chave: valor` com outra chave (`-- obs: ...`), que só geram um aviso no
+    /// log para apontar diretivas digitadas errado.
+    pub fn parse(path: &Path, text: &str) -> EngineResult<Self>
+    {
+        let error = |line: usize, reason: String| EngineError::QueryFile {
+            path: path.to_path_buf(),
+            line,
+            reason,
+        };
+
+        let mut name = None;
+        let mut description = None;
+        let mut params: Vec<ParamSpec> = Vec::new();
+        let mut cache_ttl = None;
+        let mut body_start = text.lines().count();
+
+        for (index, line) in text.lines().enumerate()
+        {
+            let line_no = index + 1;
+            let Some(comment) = line.trim_start().strip_prefix("--")
+            else
+            {
+                if line.trim().is_empty()
+                {
+                    continue;
+                }
+                body_start = index;
+                break;
+            };
+
+            // Só `-- chave: valor` com chave em minúsculas é diretiva; o resto é comentário
+            let Some((key, value)) = comment
+                .split_once(':')
+                .map(|(k, v)| (k.trim(), v.trim()))
+                .filter(|(k, _)| !k.is_empty() && k.bytes().all(|b| b.is_ascii_lowercase() || b == b'_'))
+            else
+            {
+                continue;
+            };
+
+            match key
+            {
+                "name" => name = Some(value.to_string()),
+                "description" => description = Some(value.to_string()),
+                "cache_ttl" =>
+                {
+                    let secs: u64 = value
+                        .parse()
+                        .map_err(|_| error(line_no, format!("cache_ttl inválido: {:?}", value)))?;
+                    cache_ttl = Some(Duration::from_secs(secs));
+                },
+                "param" =>
+                {
+                    let param = parse_param_spec(value).map_err(|reason| error(line_no, reason))?;
+                    if params.iter().any(|p| p.name == param.name)
+                    {
+                        return Err(error(line_no, format!("parâmetro {} declarado duas vezes", param.name)));
+                    }
+                    params.push(param);
+                },
+                other => warn!(
+                    "{}:{}: \"{}\" não é diretiva do registro; linha tratada como comentário.",
+                    path.display(),
+                    line_no,
+                    other
+                ),
+            }
+        }
+
+        let sql = text
+            .lines()
+            .skip(body_start)
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .trim_end_matches(';')
+            .trim_end()
+            .to_string();
+        if sql.is_empty()
+        {
+            return Err(error(body_start + 1, "arquivo sem instrução SQL".to_string()));
+        }
+
+        // Confere os binds contra os parâmetros declarados já no carregamento. O Oracle associa
+        // os valores pela ordem em que os binds aparecem, não pelo número, então o SQL precisa
+        // usar :1, :2, ... uma vez cada e nessa ordem
+        let binds = bind_names(&sql).map_err(|e| match e
+        {
+            EngineError::SqlRejected(reason) => error(body_start + 1, reason),
+            other => other,
+        })?;
+        for (index, bind) in binds.iter().enumerate()
+        {
+            let Ok(number) = bind.parse::<usize>()
+            else
+            {
+                return Err(error(
+                    body_start + 1,
+                    format!("bind nomeado :{} não é suportado; use :1, :2, ...", bind),
+                ));
+            };
+            if number == 0
+            {
+                return Err(error(
+                    body_start + 1,
+                    "o SQL usa :0, mas os binds posicionais começam em :1".to_string(),
+                ));
+            }
+            if number != index + 1
+            {
+                let reason = if binds[..index].contains(bind)
+                {
+                    format!(
+                        "o SQL repete :{}; os binds valem pela ordem em que aparecem, declare um parâmetro por uso",
+                        number
+                    )
+                }
+                else
+                {
+                    format!(
+                        "o SQL usa :{} onde era esperado :{}; os binds precisam aparecer em ordem",
+                        number,
+                        index + 1
+                    )
+                };
+                return Err(error(body_start + 1, reason));
+            }
+        }
+        if binds.len() > params.len()
+        {
+            return Err(error(
+                body_start + 1,
+                format!(
+                    "o SQL usa :{}, mas só {} parâmetro(s) foram declarados",
+                    binds.len(),
+                    params.len()
+                ),
+            ));
+        }
+        if let Some(unused) = params.get(binds.len())
+        {
+            return Err(error(
+                body_start + 1,
+                format!("parâmetro {} (:{}) não é usado no SQL", unused.name, binds.len() + 1),
+            ));
+        }
+
+        let name = match name
+        {
+            Some(name) => name,
+            None => path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .map(str::to_string)
+                .ok_or_else(|| error(1, "não foi possível deduzir o nome da consulta".to_string()))?,
+        };
+
+        Ok(Self {
+            name,
+            description,
+            params,
+            cache_ttl,
+            sql,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Valida os argumentos contra os parâmetros declarados e monta os binds na ordem do SQL.
+    ///
+    /// Recusa argumentos não declarados, obrigatórios ausentes e valores que não convertem
+    /// para o tipo declarado, sem consultar o banco.
+    pub fn bind(&self, args: &[(&str, SqlValue)]) -> EngineResult<BoundQuery>
+    {
+        let invalid = |reason: String| EngineError::InvalidArgument(format!("consulta {}: {}", self.name, reason));
+
+        if let Some((unknown, _)) = args.iter().find(|(name, _)| !self.params.iter().any(|p| p.name == *name))
+        {
+            return Err(invalid(format!("parâmetro desconhecido: {}", unknown)));
+        }
+
+        let mut params = Vec::with_capacity(self.params.len());
+        for spec in &self.params
+        {
+            let value = match args.iter().rev().find(|(name, _)| *name == spec.name)
+            {
+                Some((_, value)) => spec
+                    .kind
+                    .coerce(value.clone())
+                    .map_err(|e| invalid(format!("parâmetro {}: {}", spec.name, e)))?,
+                None => spec
+                    .default
+                    .clone()
+                    .ok_or_else(|| invalid(format!("parâmetro obrigatório ausente: {}", spec.name)))?,
+            };
+            params.push(value);
+        }
+
+        Ok(BoundQuery {
+            name: self.name.clone(),
+            sql: self.sql.clone(),
+            params,
+            options: QueryOptions {
+                cache_ttl: self.cache_ttl,
+                ..Default::default()
+            },
+        })
+    }
+}
+
+/// Consulta nomeada com os binds já validados, pronta para o `SqlEngine`.
+#[derive(Debug, Clone)]
+pub struct BoundQuery
+{
+    pub name: String,
+    pub sql: String,
+    pub params: Vec<SqlValue>,
+    /// Opções derivadas do cabeçalho (`cache_ttl`).
+    pub options: QueryOptions,
+}
+
+/// Registro de consultas nomeadas carregadas dos arquivos `*.sql` de um diretório.
+///
+/// `reload_if_changed` relê o diretório quando algum arquivo muda; se a nova versão tiver
+/// erro, o registro mantém as consultas anteriores.
+pub struct QueryRegistry
+{
+    dir: PathBuf,
+    state: RwLock<RegistryState>,
+}
+
+#[derive(Default)]
+struct RegistryState
+{
+    queries: HashMap<String, Arc<NamedQuery>>,
+    /// Arquivos, data de modificação e tamanho na última carga.
+    fingerprint: Vec<(PathBuf, Option<SystemTime>, u64)>,
+}
+
+impl QueryRegistry
+{
+    /// Carrega as consultas de `dir` (apenas o próprio diretório, sem subdiretórios).
+    pub fn load(dir: impl Into<PathBuf>) -> EngineResult<Self>
+    {
+        let dir = dir.into();
+        let state = read_dir_state(&dir)?;
+        info!("{} consultas carregadas de {}.", state.queries.len(), dir.display());
+        Ok(Self {
+            dir,
+            state: RwLock::new(state),
+        })
+    }
+
+    pub fn dir(&self) -> &Path
+    {
+        &self.dir
+    }
+
+    /// Consulta registrada com o nome informado.
+    pub fn get(&self, name: &str) -> EngineResult<Arc<NamedQuery>>
+    {
+        self.read()
+            .queries
+            .get(name)
+            .cloned()
+            .ok_or_else(|| EngineError::UnknownQuery(name.to_string()))
+    }
+
+    /// Nomes das consultas registradas, em ordem alfabética.
+    pub fn names(&self) -> Vec<String>
+    {
+        let mut names: Vec<String> = self.read().queries.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Atalho para `get(name)?.bind(args)`.
+    pub fn bind(&self, name: &str, args: &[(&str, SqlValue)]) -> EngineResult<BoundQuery>
+    {
+        self.get(name)?.bind(args)
+    }
+
+    /// Executa a consulta nomeada no engine. O `cache_ttl` do cabeçalho vale quando
+    /// `options.cache_ttl` não estiver definido.
+    pub fn query_df(
+        &self,
+        engine: &dyn SqlEngine,
+        name: &str,
+        args: &[(&str, SqlValue)],
+        options: &QueryOptions,
+    ) -> EngineResult<DataFrame>
+    {
+        let query = self.bind(name, args)?;
+        let mut options = options.clone();
+        options.cache_ttl = options.cache_ttl.or(query.options.cache_ttl);
+        engine.query_df(&query.sql, &query.params, &options, None)
+    }
+
+    /// Relê todos os arquivos do diretório. Retorna quantas consultas foram carregadas.
+    pub fn reload(&self) -> EngineResult<usize>
+    {
+        let state = read_dir_state(&self.dir)?;
+        let count = state.queries.len();
+        *self.state.write().unwrap_or_else(PoisonError::into_inner) = state;
+        info!("{} consultas recarregadas de {}.", count, self.dir.display());
+        Ok(count)
+    }
+
+    /// Relê o diretório se algum arquivo `.sql` foi criado, removido ou alterado desde a
+    /// última carga. Retorna se houve recarga.
+    pub fn reload_if_changed(&self) -> EngineResult<bool>
+    {
+        let fingerprint = dir_fingerprint(&self.dir)?;
+        if fingerprint == self.read().fingerprint
+        {
+            return Ok(false);
+        }
+        self.reload()?;
+        Ok(true)
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, RegistryState>
+    {
+        self.state.read().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn read_dir_state(dir: &Path) -> EngineResult<RegistryState>
+{
+    let fingerprint = dir_fingerprint(dir)?;
+    let mut queries: HashMap<String, Arc<NamedQuery>> = HashMap::new();
+
+    for (path, _, _) in &fingerprint
+    {
+        let text = fs::read_to_string(path).map_err(|e| EngineError::io(path, e))?;
+        let query = NamedQuery::parse(path, &text)?;
+        if let Some(previous) = queries.get(&query.name)
+        {
+            return Err(EngineError::QueryFile {
+                path: path.clone(),
+                line: 1,
+                reason: format!("consulta {} já definida em {}", query.name, previous.path.display()),
+            });
+        }
+        queries.insert(query.name.clone(), Arc::new(query));
+    }
+
+    Ok(RegistryState { queries, fingerprint })
+}
+
+/// Arquivos `.sql` do diretório, ordenados, com data de modificação e tamanho.
+fn dir_fingerprint(dir: &Path) -> EngineResult<Vec<(PathBuf, Option<SystemTime>, u64)>>
+{
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|e| EngineError::io(dir, e))?
+    {
+        let entry = entry.map_err(|e| EngineError::io(dir, e))?;
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("sql")
+        {
+            continue;
+        }
+        let metadata = entry.metadata().map_err(|e| EngineError::io(&path, e))?;
+        if metadata.is_file()
+        {
+            files.push((path, metadata.modified().ok(), metadata.len()));
+        }
+    }
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(files)
+}
+
+/// Lê `nome tipo [= padrão]`.
+fn parse_param_spec(text: &str) -> Result<ParamSpec, String>
+{
+    let (decl, default) = match text.split_once('=')
+    {
+        Some((decl, default)) => (decl.trim(), Some(default.trim())),
+        None => (text.trim(), None),
+    };
+
+    let mut parts = decl.split_whitespace();
+    let (Some(name), Some(kind), None) = (parts.next(), parts.next(), parts.next())
+    else
+    {
+        return Err(format!("parâmetro inválido {:?}; use `nome tipo [= padrão]`", text));
+    };
+    let kind = ParamType::parse(kind).ok_or_else(|| {
+        format!(
+            "tipo desconhecido {:?} em {}; use int, float, text, bool, date ou datetime",
+            kind, name
+        )
+    })?;
+
+    let default = match default
+    {
+        None => None,
+        Some(value) if value.eq_ignore_ascii_case("null") => Some(SqlValue::Null),
+        Some(value) =>
+        {
+            // Aspas simples permitem padrões com espaços ou vazios
+            let value = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')).unwrap_or(value);
+            let value = kind
+                .coerce(SqlValue::Text(value.to_string()))
+                .map_err(|e| format!("padrão inválido para {}: {}", name, e))?;
+            Some(value)
+        },
+    };
+
+    Ok(ParamSpec {
+        name: name.to_string(),
+        kind,
+        default,
+    })
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /// Lê a consulta; o erro vira `(linha, motivo)` para comparar direto.
+    fn parse(text: &str) -> Result<NamedQuery, (usize, String)>
+    {
+        NamedQuery::parse(Path::new("consultas/chassi_loc.sql"), text).map_err(|e| match e
+        {
+            EngineError::QueryFile { line, reason, .. } => (line, reason),
+            other => panic!("esperado EngineError::QueryFile, veio {:?}", other),
+        })
+    }
+
+    #[test]
+    fn parses_header_and_body()
+    {
+        let query = parse(
+            "-- name: chassi\n\
+             -- description: Localização dos chassis\n\
+             -- Comentário livre\n\
+             -- obs: diretiva desconhecida vira comentário\n\
+             -- param: status text = 'A B'\n\
+             -- param: limite int = 1000\n\
+             -- cache_ttl: 60\n\
+             \n\
+             SELECT * FROM t\n WHERE status = :1 AND ROWNUM <= :2;\n",
+        )
+        .unwrap();
+
+        assert_eq!(query.name, "chassi");
+        assert_eq!(query.description.as_deref(), Some("Localização dos chassis"));
+        assert_eq!(query.cache_ttl, Some(Duration::from_secs(60)));
+        assert_eq!(query.sql, "SELECT * FROM t\n WHERE status = :1 AND ROWNUM <= :2");
+        assert_eq!(
+            query.params,
+            vec![
+                ParamSpec {
+                    name: "status".to_string(),
+                    kind: ParamType::Text,
+                    default: Some(SqlValue::Text("A B".to_string())),
+                },
+                ParamSpec {
+                    name: "limite".to_string(),
+                    kind: ParamType::Int,
+                    default: Some(SqlValue::Int(1000)),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn name_defaults_to_file_stem()
+    {
+        let query = parse("SELECT 1 FROM dual").unwrap();
+        assert_eq!(query.name, "chassi_loc");
+        assert!(query.params.is_empty());
+    }
+
+    #[test]
+    fn rejects_inconsistent_files()
+    {
+        assert_eq!(parse("-- name: vazio\n\n").unwrap_err().1, "arquivo sem instrução SQL");
+        assert_eq!(parse("-- cache_ttl: um minuto\nSELECT 1 FROM dual").unwrap_err().0, 1);
+        assert_eq!(parse("-- param: id long\nSELECT :1 FROM dual").unwrap_err().0, 1);
+        assert_eq!(parse("-- param: id int = x\nSELECT :1 FROM dual").unwrap_err().0, 1);
+        assert_eq!(
+            parse("-- param: id int\n-- param: id text\nSELECT :1 FROM dual")
+                .unwrap_err()
+                .0,
+            2
+        );
+
+        let (line, reason) = parse("-- param: id int\nSELECT :0 FROM dual").unwrap_err();
+        assert_eq!(
+            (line, reason.as_str()),
+            (2, "o SQL usa :0, mas os binds posicionais começam em :1")
+        );
+
+        let (_, reason) = parse("-- param: id int\nSELECT :2 FROM dual").unwrap_err();
+        assert!(reason.contains(":2"), "{}", reason);
+
+        let (_, reason) = parse("-- param: id int\n-- param: nome text\nSELECT :1 FROM dual").unwrap_err();
+        assert!(reason.contains("nome (:2)"), "{}", reason);
+    }
+
+    #[test]
+    fn binds_inside_literals_and_comments_are_ignored()
+    {
+        let query = parse(
+            "-- param: id int\n\
+             SELECT ':2', q'[:3 é texto]', \":4\" /* :5 */ FROM t -- :6\nWHERE a = :1",
+        )
+        .unwrap();
+        assert_eq!(query.params.len(), 1);
+
+        let (line, _) = parse("-- param: id int\nSELECT q'[aberto FROM t WHERE a = :1").unwrap_err();
+        assert_eq!(line, 2);
+    }
+
+    #[test]
+    fn rejects_repeated_out_of_order_and_named_binds()
+    {
+        let two = "-- param: a int\n-- param: b int\n";
+
+        let (line, reason) = parse(&format!("{}SELECT * FROM t WHERE x = :1 OR y = :1 AND z = :2", two)).unwrap_err();
+        assert_eq!(line, 3);
+        assert!(reason.contains("repete :1"), "{}", reason);
+
+        let (_, reason) = parse(&format!("{}SELECT * FROM t WHERE x = :2 AND y = :1", two)).unwrap_err();
+        assert!(reason.contains(":2 onde era esperado :1"), "{}", reason);
+
+        let (_, reason) = parse("-- param: a int\nSELECT * FROM t WHERE x = :a").unwrap_err();
+        assert!(reason.contains("bind nomeado :a"), "{}", reason);
+    }
+
+    #[test]
+    fn bind_coerces_and_fills_defaults()
+    {
+        let query =
+            parse("-- param: id int\n-- param: desde date = 2024-01-31\nSELECT * FROM t WHERE id = :1 AND d >= :2").unwrap();
+
+        let bound = query.bind(&[("id", SqlValue::Text(" 42 ".to_string()))]).unwrap();
+        assert_eq!(
+            bound.params,
+            vec![
+                SqlValue::Int(42),
+                SqlValue::Date(NaiveDate::from_ymd_opt(2024, 1, 31).unwrap())
+            ]
+        );
+
+        assert!(matches!(query.bind(&[]), Err(EngineError::InvalidArgument(_))));
+        assert!(matches!(
+            query.bind(&[("id", SqlValue::Int(1)), ("outro", SqlValue::Int(2))]),
+            Err(EngineError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            query.bind(&[("id", SqlValue::Text("x".to_string()))]),
+            Err(EngineError::InvalidArgument(_))
+        ));
+    }
+}
@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+use rand::Rng;
+
+/// Política de novas tentativas para leituras que falham por perda de conexão.
+///
+/// A espera antes da tentativa `n` é `initial_backoff * multiplier^(n-1)`, limitada a
+/// `max_backoff`, com uma variação aleatória de até `jitter` (fração) para que vários
+/// clientes não reconectem ao mesmo tempo.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy
+{
+    /// Total de tentativas, contando a primeira; `1` desativa as novas tentativas.
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub multiplier: f64,
+    /// Fração da espera sorteada para mais ou para menos (0.0 a 1.0).
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy
+{
+    fn default() -> Self
+    {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy
+{
+    /// Política sem novas tentativas.
+    pub fn none() -> Self
+    {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Espera antes de repetir, depois de `attempt` tentativas falhas (a partir de 1).
+    pub fn backoff(&self, attempt: u32) -> Duration
+    {
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let base = (self.initial_backoff.as_secs_f64() * self.multiplier.powi(exponent)).min(self.max_backoff.as_secs_f64());
+
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        let factor = if jitter > 0.0
+        {
+            rand::thread_rng().gen_range(1.0 - jitter..=1.0 + jitter)
+        }
+        else
+        {
+            1.0
+        };
+        Duration::from_secs_f64(base * factor)
+    }
+}
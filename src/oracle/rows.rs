@@ -0,0 +1,603 @@
+use std::fmt;
+
+use chrono::DateTime;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use oracle::sql_type::ToSql;
+use polars::prelude::*;
+use serde::de;
+use serde::de::value::StrDeserializer;
+use serde::de::DeserializeOwned;
+use serde::de::DeserializeSeed;
+use serde::de::IntoDeserializer;
+use serde::de::MapAccess;
+use serde::de::SeqAccess;
+use serde::de::Visitor;
+use serde::forward_to_deserialize_any;
+use serde::Deserializer;
+
+use super::connection::EngineOracle;
+use super::engine::SqlEngine;
+use super::engine::SqlValue;
+use super::error::EngineError;
+use super::error::EngineResult;
+use super::options::QueryOptions;
+use super::types::parse_bool;
+
+impl EngineOracle
+{
+    /// Executa a query e desserializa cada linha em `T`.
+    ///
+    /// As colunas são associadas aos campos pelo nome, sem diferenciar maiúsculas (o Oracle
+    /// devolve `CHASSI`, o campo se chama `chassi`); `#[serde(rename)]` também vale. Colunas
+    /// anuláveis pedem campos `Option<_>`.
+    ///
+    /// ```ignore
+    /// #[derive(Deserialize)]
+    /// struct ChassisLocation { chassi: String, local: Option<String>, lastupddttm: NaiveDateTime }
+    ///
+    /// let rows: Vec<ChassisLocation> = engine.query_as("SELECT CHASSI, LOCAL, LASTUPDDTTM FROM ...", &[])?;
+    /// ```
+    pub fn query_as<T: DeserializeOwned>(&self, sql: &str, params: &[&dyn ToSql]) -> EngineResult<Vec<T>>
+    {
+        self.query_as_with(sql, params, &QueryOptions::default())
+    }
+
+    /// Igual a `query_as`, com opções de conversão explícitas.
+    ///
+    /// Colunas `DATE` chegam como `Datetime`; para campos `NaiveDate`, use
+    /// `DateMapping::Date` ou `QueryOptions::column_type(coluna, DataType::Date)`.
+    pub fn query_as_with<T: DeserializeOwned>(
+        &self,
+        sql: &str,
+        params: &[&dyn ToSql],
+        options: &QueryOptions,
+    ) -> EngineResult<Vec<T>>
+    {
+        let df = self.query_to_polars_df_with(sql, params, options)?;
+        from_df(&df).map_err(|e| e.with_sql(sql))
+    }
+}
+
+/// `query_as` para qualquer `SqlEngine` (Oracle, SQLite ou com cache).
+pub fn query_as<T: DeserializeOwned>(
+    engine: &dyn SqlEngine,
+    sql: &str,
+    params: &[SqlValue],
+    options: &QueryOptions,
+) -> EngineResult<Vec<T>>
+{
+    let df = engine.query_df(sql, params, options, None)?;
+    from_df(&df).map_err(|e| e.with_sql(sql))
+}
+
+/// Desserializa cada linha do DataFrame em `T`.
+///
+/// Para structs, todo campo precisa de uma coluna e toda coluna de um campo; a falta ou a
+/// sobra gera `EngineError::Mapping` com o nome, mesmo sem linhas. Tuplas recebem as colunas
+/// na ordem e mapas (`HashMap<String, _>`), todas as colunas com o nome original.
+pub fn from_df<T: DeserializeOwned>(df: &DataFrame) -> EngineResult<Vec<T>>
+{
+    let columns: Vec<&Series> = df.get_columns().iter().map(|c| c.as_materialized_series()).collect();
+    let mapping = match struct_fields::<T>()
+    {
+        Some(fields) => map_fields::<T>(fields, &columns)?,
+        None => columns.iter().map(|s| (s.name().as_str(), *s)).collect(),
+    };
+
+    (0..df.height())
+        .map(|row| {
+            T::deserialize(RowDeserializer { columns: &mapping, row }).map_err(|e| match e.field
+            {
+                Some(field) => EngineError::Conversion {
+                    column: field,
+                    row: row + 1,
+                    message: e.message,
+                    sql_id: None,
+                },
+                None => EngineError::Mapping {
+                    field: std::any::type_name::<T>().to_string(),
+                    message: format!("linha {}: {}", row + 1, e.message),
+                },
+            })
+        })
+        .collect()
+}
+
+/// Associa cada campo à sua coluna, na ordem dos campos.
+fn map_fields<'a, T>(fields: &'static [&'static str], columns: &[&'a Series]) -> EngineResult<Vec<(&'a str, &'a Series)>>
+{
+    let mut mapping = Vec::with_capacity(fields.len());
+    for field in fields
+    {
+        let column = columns
+            .iter()
+            .find(|s| s.name().eq_ignore_ascii_case(field))
+            .ok_or_else(|| EngineError::Mapping {
+                field: field.to_string(),
+                message: "nenhuma coluna com esse nome no resultado".to_string(),
+            })?;
+        mapping.push((*field, *column));
+    }
+
+    if let Some(extra) = columns
+        .iter()
+        .find(|s| !fields.iter().any(|f| s.name().eq_ignore_ascii_case(f)))
+    {
+        return Err(EngineError::Mapping {
+            field: extra.name().to_string(),
+            message: format!("a coluna não corresponde a nenhum campo de {}", std::any::type_name::<T>()),
+        });
+    }
+    Ok(mapping)
+}
+
+/// Campos de `T`, quando `T` é uma struct (obtidos sem desserializar nenhuma linha).
+fn struct_fields<T: DeserializeOwned>() -> Option<&'static [&'static str]>
+{
+    match T::deserialize(FieldProbe)
+    {
+        Err(ProbeError(fields)) => fields,
+        Ok(_) => None,
+    }
+}
+
+/// Erro de desserialização de uma linha, com o campo em que ocorreu.
+#[derive(Debug)]
+struct DeError
+{
+    field: Option<String>,
+    message: String,
+}
+
+impl DeError
+{
+    fn in_field(mut self, field: &str) -> Self
+    {
+        self.field.get_or_insert_with(|| field.to_string());
+        self
+    }
+}
+
+impl fmt::Display for DeError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for DeError
+{
+}
+
+impl de::Error for DeError
+{
+    fn custom<M: fmt::Display>(msg: M) -> Self
+    {
+        Self {
+            field: None,
+            message: msg.to_string(),
+        }
+    }
+}
+
+/// Uma linha: mapa campo → valor para structs e mapas, sequência para tuplas.
+struct RowDeserializer<'a>
+{
+    columns: &'a [(&'a str, &'a Series)],
+    row: usize,
+}
+
+impl<'de> Deserializer<'de> for RowDeserializer<'_>
+{
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError>
+    {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError>
+    {
+        visitor.visit_map(RowAccess {
+            columns: self.columns.iter(),
+            row: self.row,
+            current: None,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError>
+    {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError>
+    {
+        visitor.visit_seq(RowAccess {
+            columns: self.columns.iter(),
+            row: self.row,
+            current: None,
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, DeError>
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, DeError>
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct enum identifier ignored_any
+    }
+}
+
+struct RowAccess<'a, I>
+{
+    columns: I,
+    row: usize,
+    current: Option<&'a (&'a str, &'a Series)>,
+}
+
+impl<'a, I: Iterator<Item = &'a (&'a str, &'a Series)>> RowAccess<'a, I>
+{
+    fn cell<'de, S: DeserializeSeed<'de>>(&self, column: &(&str, &Series), seed: S) -> Result<S::Value, DeError>
+    {
+        let (name, series) = column;
+        let value = series
+            .get(self.row)
+            .map_err(|e| <DeError as de::Error>::custom(e).in_field(name))?;
+        seed.deserialize(CellDeserializer(value)).map_err(|e| e.in_field(name))
+    }
+}
+
+impl<'de, 'a, I: Iterator<Item = &'a (&'a str, &'a Series)>> MapAccess<'de> for RowAccess<'a, I>
+{
+    type Error = DeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, DeError>
+    {
+        self.current = self.columns.next();
+        match self.current
+        {
+            Some((name, _)) =>
+            {
+                let key: StrDeserializer<'_, DeError> = name.into_deserializer();
+                seed.deserialize(key).map(Some)
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, DeError>
+    {
+        let column = self.current.ok_or_else(|| de::Error::custom("valor pedido antes da chave"))?;
+        self.cell(column, seed)
+    }
+}
+
+impl<'de, 'a, I: Iterator<Item = &'a (&'a str, &'a Series)>> SeqAccess<'de> for RowAccess<'a, I>
+{
+    type Error = DeError;
+
+    fn next_element_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<Option<S::Value>, DeError>
+    {
+        match self.columns.next()
+        {
+            Some(column) => self.cell(column, seed).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Uma célula. Datas viram texto: ISO com `T` quando o tipo pede uma string (chrono) e com
+/// espaço quando aceita qualquer valor (crate `time`, com `serde-human-readable`).
+struct CellDeserializer<'a>(AnyValue<'a>);
+
+impl CellDeserializer<'_>
+{
+    fn as_i64(&self) -> Option<i64>
+    {
+        match &self.0
+        {
+            AnyValue::Float32(v) if v.fract() == 0.0 => Some(*v as i64),
+            AnyValue::Float64(v) if v.fract() == 0.0 => Some(*v as i64),
+            AnyValue::Decimal(v, scale) => i64::try_from(*v / 10i128.pow(*scale as u32))
+                .ok()
+                .filter(|_| *v % 10i128.pow(*scale as u32) == 0),
+            AnyValue::String(text) => text.trim().parse().ok(),
+            AnyValue::StringOwned(text) => text.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64>
+    {
+        match &self.0
+        {
+            AnyValue::Decimal(v, scale) => Some(*v as f64 / 10f64.powi(*scale as i32)),
+            AnyValue::String(text) => text.trim().parse().ok(),
+            AnyValue::StringOwned(text) => text.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Texto do valor; `iso` escolhe o separador entre data e hora.
+    fn text(&self, iso: bool) -> Option<String>
+    {
+        let text = match &self.0
+        {
+            AnyValue::String(text) => text.to_string(),
+            AnyValue::StringOwned(text) => text.to_string(),
+            AnyValue::Date(days) => date_from_days(*days)?.to_string(),
+            AnyValue::Datetime(v, unit, tz) => datetime_text(*v, *unit, tz.is_some(), iso)?,
+            AnyValue::DatetimeOwned(v, unit, tz) => datetime_text(*v, *unit, tz.is_some(), iso)?,
+            AnyValue::Decimal(v, scale) => decimal_text(*v, *scale),
+            _ => return None,
+        };
+        Some(text)
+    }
+}
+
+impl<'de> Deserializer<'de> for CellDeserializer<'_>
+{
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError>
+    {
+        match &self.0
+        {
+            AnyValue::Null => visitor.visit_none(),
+            AnyValue::Boolean(v) => visitor.visit_bool(*v),
+            AnyValue::Int8(_) | AnyValue::Int16(_) | AnyValue::Int32(_) | AnyValue::Int64(_) =>
+            {
+                visitor.visit_i64(self.0.extract::<i64>().unwrap_or_default())
+            },
+            AnyValue::UInt8(_) | AnyValue::UInt16(_) | AnyValue::UInt32(_) | AnyValue::UInt64(_) =>
+            {
+                visitor.visit_u64(self.0.extract::<u64>().unwrap_or_default())
+            },
+            AnyValue::Float32(v) => visitor.visit_f64(*v as f64),
+            AnyValue::Float64(v) => visitor.visit_f64(*v),
+            AnyValue::Decimal(..) => visitor.visit_f64(self.as_f64().unwrap_or_default()),
+            AnyValue::Binary(v) => visitor.visit_bytes(v),
+            AnyValue::BinaryOwned(v) => visitor.visit_bytes(v),
+            _ => match self.text(false)
+            {
+                Some(text) => visitor.visit_string(text),
+                None => visitor.visit_string(self.0.to_string()),
+            },
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError>
+    {
+        match self.0
+        {
+            AnyValue::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError>
+    {
+        match &self.0
+        {
+            AnyValue::Null => visitor.visit_none(),
+            AnyValue::String(text) => visitor.visit_str(text),
+            _ => match self.text(true)
+            {
+                Some(text) => visitor.visit_string(text),
+                None => visitor.visit_string(self.0.to_string()),
+            },
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError>
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError>
+    {
+        // Flags PeopleSoft: `Y`/`N` em VARCHAR2 ou `NUMBER(1)`
+        match &self.0
+        {
+            AnyValue::String(text) => visitor.visit_bool(parse_bool(text).map_err(de::Error::custom)?),
+            AnyValue::StringOwned(text) => visitor.visit_bool(parse_bool(text).map_err(de::Error::custom)?),
+            AnyValue::Int64(v) => visitor.visit_bool(*v != 0),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError>
+    {
+        // NUMBER sem precisão chega como Float64, mesmo guardando inteiros
+        match self.as_i64()
+        {
+            Some(v) => visitor.visit_i64(v),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError>
+    {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError>
+    {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError>
+    {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError>
+    {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError>
+    {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError>
+    {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError>
+    {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError>
+    {
+        match self.as_f64()
+        {
+            Some(v) => visitor.visit_f64(v),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError>
+    {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, DeError>
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError>
+    {
+        // Enums sem dados, a partir do texto da coluna (ex.: status)
+        let text = self.text(true).unwrap_or_else(|| self.0.to_string());
+        visitor.visit_enum(text.trim().to_string().into_deserializer())
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128 char bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// Deserializer que só descobre os campos da struct de destino.
+struct FieldProbe;
+
+#[derive(Debug)]
+struct ProbeError(Option<&'static [&'static str]>);
+
+impl fmt::Display for ProbeError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        f.write_str("sonda de campos")
+    }
+}
+
+impl std::error::Error for ProbeError
+{
+}
+
+impl de::Error for ProbeError
+{
+    fn custom<M: fmt::Display>(_msg: M) -> Self
+    {
+        Self(None)
+    }
+}
+
+impl<'de> Deserializer<'de> for FieldProbe
+{
+    type Error = ProbeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, ProbeError>
+    {
+        Err(ProbeError(None))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, ProbeError>
+    {
+        Err(ProbeError(Some(fields)))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier ignored_any
+    }
+}
+
+fn date_from_days(days: i32) -> Option<NaiveDate>
+{
+    DateTime::UNIX_EPOCH
+        .date_naive()
+        .checked_add_signed(chrono::Duration::days(days as i64))
+}
+
+fn datetime_text(value: i64, unit: TimeUnit, utc: bool, iso: bool) -> Option<String>
+{
+    let dt: NaiveDateTime = match unit
+    {
+        TimeUnit::Milliseconds => DateTime::from_timestamp_millis(value)?,
+        TimeUnit::Microseconds => DateTime::from_timestamp_micros(value)?,
+        TimeUnit::Nanoseconds => DateTime::from_timestamp_nanos(value),
+    }
+    .naive_utc();
+
+    let text = match (iso, utc)
+    {
+        (true, false) => dt.format("%Y-%m-%dT%H:%M:%S%.f").to_string(),
+        (true, true) => dt.format("%Y-%m-%dT%H:%M:%S%.fZ").to_string(),
+        // Formato padrão do crate `time`, que exige a fração de segundo
+        (false, false) => dt.format("%Y-%m-%d %H:%M:%S.%f").to_string(),
+        (false, true) => dt.format("%Y-%m-%d %H:%M:%S.%f +00:00:00").to_string(),
+    };
+    Some(text)
+}
+
+fn decimal_text(value: i128, scale: usize) -> String
+{
+    if scale == 0
+    {
+        return value.to_string();
+    }
+    let factor = 10i128.pow(scale as u32);
+    let sign = if value < 0 { "-" } else { "" };
+    format!(
+        "{}{}.{:0width$}",
+        sign,
+        (value / factor).abs(),
+        (value % factor).abs(),
+        width = scale
+    )
+}
@@ -0,0 +1,144 @@
+use std::env;
+use std::sync::OnceLock;
+
+use log::info;
+use oracle::Connection;
+
+use super::error::EngineError;
+use super::error::EngineResult;
+use super::sql::quote_identifier;
+
+/// Identificação da sessão vista pelos DBAs em `V$SESSION` (`MODULE`, `ACTION` e
+/// `CLIENT_IDENTIFIER`), a mesma que `DBMS_APPLICATION_INFO` definiria.
+///
+/// Os valores são atributos da conexão, enviados junto com o próximo round-trip, sem uma
+/// chamada extra ao banco. Campos `None` usam o valor da configuração do engine.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionTag
+{
+    /// Até 48 bytes; sem valor configurado, o nome do executável.
+    pub module: Option<String>,
+    /// Até 32 bytes.
+    pub action: Option<String>,
+    /// Até 64 bytes (usuário final, por exemplo).
+    pub client_identifier: Option<String>,
+}
+
+impl SessionTag
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    pub fn module(mut self, module: impl Into<String>) -> Self
+    {
+        self.module = Some(module.into());
+        self
+    }
+
+    pub fn action(mut self, action: impl Into<String>) -> Self
+    {
+        self.action = Some(action.into());
+        self
+    }
+
+    pub fn client_identifier(mut self, client_identifier: impl Into<String>) -> Self
+    {
+        self.client_identifier = Some(client_identifier.into());
+        self
+    }
+
+    /// Aplica os valores na conexão, completando com `defaults`.
+    ///
+    /// Os três atributos são sempre redefinidos, para que uma conexão devolvida ao pool não
+    /// carregue a identificação da chamada anterior.
+    pub(crate) fn apply(&self, conn: &Connection, defaults: &SessionTag) -> EngineResult<()>
+    {
+        let module = self.module.as_ref().or(defaults.module.as_ref());
+        match module
+        {
+            Some(module) => conn.set_module(module)?,
+            None => conn.set_module(program_name())?,
+        }
+        conn.set_action(self.action.as_ref().or(defaults.action.as_ref()).map_or("", String::as_str))?;
+        conn.set_client_identifier(
+            self.client_identifier
+                .as_ref()
+                .or(defaults.client_identifier.as_ref())
+                .map_or("", String::as_str),
+        )?;
+        Ok(())
+    }
+}
+
+/// Ajustes de sessão do engine: a identificação padrão e as instruções executadas em cada
+/// conexão nova (a conexão única, inclusive ao reconectar, e cada sessão criada pelo pool).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionConfig
+{
+    pub tag: SessionTag,
+    /// `NLS_DATE_FORMAT`, como `YYYY-MM-DD HH24:MI:SS`.
+    pub nls_date_format: Option<String>,
+    pub nls_timestamp_format: Option<String>,
+    /// `NLS_NUMERIC_CHARACTERS`: separador decimal e de milhar, como `.,`.
+    pub nls_numeric_characters: Option<String>,
+    /// Schema usado para nomes sem dono (`CURRENT_SCHEMA`), como `SYSADM`.
+    pub current_schema: Option<String>,
+    /// Instruções adicionais, executadas depois das anteriores e na ordem informada.
+    pub statements: Vec<String>,
+}
+
+impl SessionConfig
+{
+    /// Instruções executadas em cada conexão nova.
+    pub fn init_statements(&self) -> EngineResult<Vec<String>>
+    {
+        let mut statements = Vec::new();
+        let nls = [
+            ("NLS_DATE_FORMAT", &self.nls_date_format),
+            ("NLS_TIMESTAMP_FORMAT", &self.nls_timestamp_format),
+            ("NLS_NUMERIC_CHARACTERS", &self.nls_numeric_characters),
+        ];
+        for (parameter, value) in nls
+        {
+            if let Some(value) = value
+            {
+                statements.push(format!("ALTER SESSION SET {} = '{}'", parameter, value.replace('\'', "''")));
+            }
+        }
+        if let Some(schema) = &self.current_schema
+        {
+            statements.push(format!("ALTER SESSION SET CURRENT_SCHEMA = {}", quote_identifier(schema)?));
+        }
+        statements.extend(self.statements.iter().cloned());
+        Ok(statements)
+    }
+
+    /// Executa as instruções de sessão na conexão recém-criada.
+    pub(crate) fn init(&self, conn: &Connection) -> EngineResult<()>
+    {
+        let statements = self.init_statements()?;
+        for sql in &statements
+        {
+            conn.execute(sql, &[]).map_err(|e| EngineError::from(e).with_sql(sql))?;
+        }
+        if !statements.is_empty()
+        {
+            info!("{} ajustes de sessão aplicados.", statements.len());
+        }
+        Ok(())
+    }
+}
+
+/// Nome do executável, o `MODULE` que o Oracle Client informaria por padrão.
+fn program_name() -> &'static str
+{
+    static NAME: OnceLock<String> = OnceLock::new();
+    NAME.get_or_init(|| {
+        env::current_exe()
+            .ok()
+            .and_then(|path| path.file_stem().map(|name| name.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| env!("CARGO_PKG_NAME").to_string())
+    })
+}
@@ -0,0 +1,204 @@
+use super::error::EngineError;
+use super::error::EngineResult;
+
+/// Palavras reservadas do SQL do Oracle (`V$RESERVED_WORDS` com `RESERVED = 'Y'`), em ordem
+/// alfabética. Não podem ser usadas sem aspas como nome de coluna ou tabela.
+const RESERVED_WORDS: [&str; 110] = [
+    "ACCESS",
+    "ADD",
+    "ALL",
+    "ALTER",
+    "AND",
+    "ANY",
+    "AS",
+    "ASC",
+    "AUDIT",
+    "BETWEEN",
+    "BY",
+    "CHAR",
+    "CHECK",
+    "CLUSTER",
+    "COLUMN",
+    "COLUMN_VALUE",
+    "COMMENT",
+    "COMPRESS",
+    "CONNECT",
+    "CREATE",
+    "CURRENT",
+    "DATE",
+    "DECIMAL",
+    "DEFAULT",
+    "DELETE",
+    "DESC",
+    "DISTINCT",
+    "DROP",
+    "ELSE",
+    "EXCLUSIVE",
+    "EXISTS",
+    "FILE",
+    "FLOAT",
+    "FOR",
+    "FROM",
+    "GRANT",
+    "GROUP",
+    "HAVING",
+    "IDENTIFIED",
+    "IMMEDIATE",
+    "IN",
+    "INCREMENT",
+    "INDEX",
+    "INITIAL",
+    "INSERT",
+    "INTEGER",
+    "INTERSECT",
+    "INTO",
+    "IS",
+    "LEVEL",
+    "LIKE",
+    "LOCK",
+    "LONG",
+    "MAXEXTENTS",
+    "MINUS",
+    "MLSLABEL",
+    "MODE",
+    "MODIFY",
+    "NESTED_TABLE_ID",
+    "NOAUDIT",
+    "NOCOMPRESS",
+    "NOT",
+    "NOWAIT",
+    "NULL",
+    "NUMBER",
+    "OF",
+    "OFFLINE",
+    "ON",
+    "ONLINE",
+    "OPTION",
+    "OR",
+    "ORDER",
+    "PCTFREE",
+    "PRIOR",
+    "PUBLIC",
+    "RAW",
+    "RENAME",
+    "RESOURCE",
+    "REVOKE",
+    "ROW",
+    "ROWID",
+    "ROWNUM",
+    "ROWS",
+    "SELECT",
+    "SESSION",
+    "SET",
+    "SHARE",
+    "SIZE",
+    "SMALLINT",
+    "START",
+    "SUCCESSFUL",
+    "SYNONYM",
+    "SYSDATE",
+    "TABLE",
+    "THEN",
+    "TO",
+    "TRIGGER",
+    "UID",
+    "UNION",
+    "UNIQUE",
+    "UPDATE",
+    "USER",
+    "VALIDATE",
+    "VALUES",
+    "VARCHAR",
+    "VARCHAR2",
+    "VIEW",
+    "WHENEVER",
+    "WHERE",
+    "WITH",
+];
+
+/// Converte um nome em identificador Oracle seguro para interpolar no SQL.
+///
+/// Nomes simples (`CHASSI`, `dt_entrada`) ficam sem aspas e em maiúsculas, como o Oracle os
+/// armazena; os demais (espaços, acentos, cabeçalhos de Excel) são colocados entre aspas.
+/// Palavras reservadas (`Date`, `Level`, `Size`) também vão entre aspas, em maiúsculas
+/// (`"DATE"`), para continuar valendo o mesmo nome que o Oracle armazena.
+pub fn quote_identifier(name: &str) -> EngineResult<String>
+{
+    let name = name.trim();
+    if name.is_empty() || name.contains('"') || name.contains('\0')
+    {
+        return Err(EngineError::InvalidArgument(format!("identificador inválido: {:?}", name)));
+    }
+
+    let mut chars = name.chars();
+    let simple = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '$' | '#'));
+
+    if simple
+    {
+        let upper = name.to_ascii_uppercase();
+        if RESERVED_WORDS.binary_search(&upper.as_str()).is_ok()
+        {
+            Ok(format!("\"{}\"", upper))
+        }
+        else
+        {
+            Ok(upper)
+        }
+    }
+    else
+    {
+        Ok(format!("\"{}\"", name))
+    }
+}
+
+/// Converte `tabela` ou `schema.tabela` em nome qualificado seguro.
+pub fn quote_table_name(table: &str) -> EngineResult<String>
+{
+    let parts = table
+        .split('.')
+        .map(quote_identifier)
+        .collect::<EngineResult<Vec<String>>>()?;
+
+    if parts.len() > 2
+    {
+        return Err(EngineError::InvalidArgument(format!("nome de tabela inválido: {:?}", table)));
+    }
+    Ok(parts.join("."))
+}
+
+/// Converte `rotina`, `pacote.rotina` ou `schema.pacote.rotina` em nome qualificado seguro.
+pub fn quote_routine_name(routine: &str) -> EngineResult<String>
+{
+    let parts = routine
+        .split('.')
+        .map(quote_identifier)
+        .collect::<EngineResult<Vec<String>>>()?;
+
+    if parts.len() > 3
+    {
+        return Err(EngineError::InvalidArgument(format!(
+            "nome de rotina inválido: {:?}",
+            routine
+        )));
+    }
+    Ok(parts.join("."))
+}
+
+/// Nome como fica gravado no dicionário: maiúsculas para nomes simples, literal para os demais.
+pub fn stored_name(name: &str) -> EngineResult<String>
+{
+    Ok(quote_identifier(name)?.trim_matches('"').to_string())
+}
+
+/// Separa `schema.tabela` nos nomes como ficam gravados no dicionário (`ALL_TABLES`, `ALL_TAB_COLUMNS`).
+///
+/// Nomes simples vão para maiúsculas; sem schema, retorna `None` no lugar do dono.
+pub fn split_table_name(table: &str) -> EngineResult<(Option<String>, String)>
+{
+    quote_table_name(table)?;
+    let mut parts = table.split('.').map(stored_name).collect::<EngineResult<Vec<String>>>()?;
+
+    let name = parts.pop().unwrap_or_default();
+    Ok((parts.pop(), name))
+}
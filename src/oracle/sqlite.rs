@@ -0,0 +1,533 @@
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+use std::time::Duration;
+use std::time::Instant;
+
+use chrono::DateTime;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use log::info;
+use polars::prelude::*;
+use rusqlite::params_from_iter;
+use rusqlite::types::ToSqlOutput;
+use rusqlite::types::Value;
+use rusqlite::types::ValueRef;
+use rusqlite::Connection;
+
+use super::cancel::CancelToken;
+use super::engine::DataFrameChunks;
+use super::engine::SqlEngine;
+use super::engine::SqlValue;
+use super::error::EngineError;
+use super::error::EngineResult;
+use super::options::FetchMode;
+use super::options::QueryOptions;
+use super::types::cast_series;
+use super::types::parse_bool;
+use super::types::parse_decimal;
+use super::types::to_timestamp;
+use super::types::ColumnBuffer;
+use super::types::DateMapping;
+
+type ConversionResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+/// `SqlEngine` sobre SQLite, para rodar handlers e binários sem Oracle nem Instant Client.
+///
+/// Segue as mesmas regras de tipagem do `EngineOracle`, a partir do tipo declarado na tabela.
+pub struct EngineSqlite
+{
+    conn: Mutex<Connection>,
+}
+
+impl EngineSqlite
+{
+    /// Abre (ou cria) o banco no arquivo informado.
+    pub fn open(path: impl AsRef<Path>) -> EngineResult<Self>
+    {
+        let path = path.as_ref();
+        let conn = Connection::open(path)?;
+        info!("SQLite aberto em: {}", path.display());
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    /// Banco temporário em memória, descartado junto com o engine.
+    pub fn open_in_memory() -> EngineResult<Self>
+    {
+        Ok(Self {
+            conn: Mutex::new(Connection::open_in_memory()?),
+        })
+    }
+
+    /// Executa um script com várias instruções separadas por `;` (criação de tabelas, carga de dados).
+    pub fn execute_batch(&self, sql: &str) -> EngineResult<()>
+    {
+        Ok(self.lock().execute_batch(sql)?)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Connection>
+    {
+        self.conn.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl SqlEngine for EngineSqlite
+{
+    fn backend_name(&self) -> &'static str
+    {
+        "sqlite"
+    }
+
+    fn query_df(
+        &self,
+        sql: &str,
+        params: &[SqlValue],
+        options: &QueryOptions,
+        cancel: Option<&CancelToken>,
+    ) -> EngineResult<DataFrame>
+    {
+        let conn = self.lock();
+        let mut stmt = conn.prepare(sql)?;
+
+        let names: Vec<String> = stmt.column_names().iter().map(|n| n.to_string()).collect();
+        let declared: Vec<Option<String>> = stmt.columns().iter().map(|c| c.decl_type().map(str::to_string)).collect();
+        info!("Colunas encontradas: {:?}", names);
+
+        // O SQLite tem tipagem dinâmica: as linhas são lidas antes de definir o tipo das
+        // colunas sem tipo declarado (expressões, agregações)
+        let mut data: Vec<Vec<Value>> = Vec::new();
+        let mut rows = stmt.query(params_from_iter(params))?;
+        while let Some(row) = rows.next()?
+        {
+            if cancel.is_some_and(|c| c.is_cancelled())
+            {
+                info!("Consulta cancelada após {} linhas.", data.len());
+                return Err(EngineError::Cancelled {
+                    sql_id: None,
+                    source: None,
+                });
+            }
+            data.push((0..names.len()).map(|i| row.get(i)).collect::<Result<_, _>>()?);
+        }
+
+        let mut columns = Vec::with_capacity(names.len());
+        for (i, name) in names.iter().enumerate()
+        {
+            let dtype = sqlite_dtype(name, declared[i].as_deref(), data.iter().map(|row| &row[i]), options);
+            let mut buffer = ColumnBuffer::new(&dtype);
+            for (row, values) in data.iter().enumerate()
+            {
+                push_value(&mut buffer, (&values[i]).into()).map_err(|e| EngineError::Conversion {
+                    column: name.clone(),
+                    row: row + 1,
+                    message: e.to_string(),
+                    sql_id: None,
+                })?;
+            }
+            let mut series = buffer.into_series(name.into())?;
+            if let Some(target) = options.type_hint(name)
+            {
+                series = cast_series(&series, target).map_err(|(row, message)| EngineError::Conversion {
+                    column: name.clone(),
+                    row: row + 1,
+                    message,
+                    sql_id: None,
+                })?;
+            }
+            columns.push(series.into_column());
+        }
+
+        let df = DataFrame::new(columns)?;
+        info!("Consulta executada; {} linhas recuperadas.", df.height());
+        Ok(df)
+    }
+
+    fn execute(&self, sql: &str, params: &[SqlValue]) -> EngineResult<u64>
+    {
+        Ok(self.lock().execute(sql, params_from_iter(params))? as u64)
+    }
+
+    /// O resultado é lido inteiro e depois fatiado: suficiente para testes e demos locais.
+    fn query_df_stream<'a>(
+        &'a self,
+        sql: &str,
+        params: &[SqlValue],
+        chunk_rows: usize,
+        options: &QueryOptions,
+    ) -> EngineResult<DataFrameChunks<'a>>
+    {
+        if chunk_rows == 0
+        {
+            return Err(EngineError::InvalidArgument("chunk_rows deve ser maior que zero".to_string()));
+        }
+
+        let df = self.query_df(sql, params, options, None)?;
+        let chunks = df.height().div_ceil(chunk_rows).max(1);
+        Ok(Box::new(
+            (0..chunks).map(move |i| Ok(df.slice((i * chunk_rows) as i64, chunk_rows))),
+        ))
+    }
+
+    fn ping(&self) -> EngineResult<Duration>
+    {
+        let started = Instant::now();
+        self.lock().query_row("SELECT 1", [], |_| Ok(()))?;
+        Ok(started.elapsed())
+    }
+}
+
+impl rusqlite::ToSql for SqlValue
+{
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>>
+    {
+        match self
+        {
+            SqlValue::Null => Ok(ToSqlOutput::Owned(Value::Null)),
+            SqlValue::Int(v) => v.to_sql(),
+            SqlValue::Float(v) => v.to_sql(),
+            SqlValue::Text(v) => v.to_sql(),
+            SqlValue::Bool(v) => v.to_sql(),
+            SqlValue::Date(v) => v.to_sql(),
+            SqlValue::Datetime(v) => v.to_sql(),
+        }
+    }
+}
+
+/// Tipo Polars de destino de uma coluna SQLite.
+///
+/// Usa o tipo declarado na tabela, pelas regras de afinidade do SQLite; sem tipo declarado,
+/// usa o tipo do primeiro valor não nulo.
+fn sqlite_dtype<'a>(
+    name: &str,
+    declared: Option<&str>,
+    mut values: impl Iterator<Item = &'a Value>,
+    options: &QueryOptions,
+) -> DataType
+{
+    if options.mode == FetchMode::Strings
+    {
+        return DataType::String;
+    }
+    if options.is_boolean_column(name)
+    {
+        return DataType::Boolean;
+    }
+
+    let Some(declared) = declared
+    else
+    {
+        return match values.find(|v| **v != Value::Null)
+        {
+            Some(Value::Integer(_)) => DataType::Int64,
+            Some(Value::Real(_)) => DataType::Float64,
+            Some(Value::Blob(_)) => DataType::Binary,
+            _ => DataType::String,
+        };
+    };
+
+    let declared = declared.to_ascii_uppercase();
+    if declared.contains("BOOL")
+    {
+        DataType::Boolean
+    }
+    else if declared.contains("DATETIME") || declared.contains("TIMESTAMP")
+    {
+        DataType::Datetime(TimeUnit::Milliseconds, None)
+    }
+    else if declared.contains("DATE")
+    {
+        match options.date_mapping
+        {
+            DateMapping::Datetime => DataType::Datetime(TimeUnit::Milliseconds, None),
+            DateMapping::Date => DataType::Date,
+        }
+    }
+    else if declared.contains("INT")
+    {
+        DataType::Int64
+    }
+    else if declared.contains("CHAR") || declared.contains("CLOB") || declared.contains("TEXT")
+    {
+        DataType::String
+    }
+    else if declared.contains("BLOB")
+    {
+        DataType::Binary
+    }
+    else if declared.starts_with("DECIMAL") || declared.starts_with("NUMERIC") || declared.starts_with("NUMBER")
+    {
+        decimal_dtype(&declared).unwrap_or(DataType::Float64)
+    }
+    else
+    {
+        DataType::Float64
+    }
+}
+
+/// Lê `DECIMAL(p, s)` / `NUMBER(p, s)`; sem precisão declarada não há como montar um `Decimal`.
+fn decimal_dtype(declared: &str) -> Option<DataType>
+{
+    let args = declared.split_once('(')?.1.strip_suffix(')')?;
+    let (precision, scale) = args.split_once(',').unwrap_or((args, "0"));
+    let precision: usize = precision.trim().parse().ok()?;
+    let scale: usize = scale.trim().parse().ok()?;
+    Some(
+        if scale == 0 && precision <= 18
+        {
+            DataType::Int64
+        }
+        else
+        {
+            DataType::Decimal(Some(precision), Some(scale))
+        },
+    )
+}
+
+/// Acrescenta o valor SQLite ao buffer, convertendo entre as classes de armazenamento quando possível.
+fn push_value(buffer: &mut ColumnBuffer, value: ValueRef<'_>) -> ConversionResult<()>
+{
+    if value == ValueRef::Null
+    {
+        push_null(buffer);
+        return Ok(());
+    }
+
+    match buffer
+    {
+        ColumnBuffer::Utf8(values) => values.push(Some(match value
+        {
+            ValueRef::Integer(v) => v.to_string(),
+            ValueRef::Real(v) => v.to_string(),
+            _ => value.as_str()?.to_string(),
+        })),
+        ColumnBuffer::Int64(values) => values.push(Some(match value
+        {
+            ValueRef::Integer(v) => v,
+            ValueRef::Real(v) if v.fract() == 0.0 => v as i64,
+            _ => value.as_str()?.trim().parse()?,
+        })),
+        ColumnBuffer::Float32(values) => values.push(Some(to_f64(value)? as f32)),
+        ColumnBuffer::Float64(values) => values.push(Some(to_f64(value)?)),
+        ColumnBuffer::Decimal { values, scale, .. } =>
+        {
+            let text = match value
+            {
+                ValueRef::Integer(v) => v.to_string(),
+                ValueRef::Real(v) => v.to_string(),
+                _ => value.as_str()?.to_string(),
+            };
+            values.push(Some(parse_decimal(&text, *scale)?));
+        },
+        ColumnBuffer::Boolean(values) => values.push(Some(match value
+        {
+            ValueRef::Integer(v) => v != 0,
+            _ => parse_bool(value.as_str()?)?,
+        })),
+        ColumnBuffer::Binary(values) => values.push(Some(match value
+        {
+            ValueRef::Blob(v) | ValueRef::Text(v) => v.to_vec(),
+            _ => return Err(format!("valor {:?} não é binário", value).into()),
+        })),
+        ColumnBuffer::Date(values) =>
+        {
+            let date = to_datetime(value)?.date();
+            values.push(Some((date - DateTime::UNIX_EPOCH.date_naive()).num_days() as i32));
+        },
+        ColumnBuffer::Datetime { values, unit, .. } =>
+        {
+            values.push(Some(to_timestamp(&to_datetime(value)?, *unit)?));
+        },
+    }
+    Ok(())
+}
+
+fn push_null(buffer: &mut ColumnBuffer)
+{
+    match buffer
+    {
+        ColumnBuffer::Utf8(values) => values.push(None),
+        ColumnBuffer::Int64(values) => values.push(None),
+        ColumnBuffer::Float32(values) => values.push(None),
+        ColumnBuffer::Float64(values) => values.push(None),
+        ColumnBuffer::Decimal { values, .. } => values.push(None),
+        ColumnBuffer::Boolean(values) => values.push(None),
+        ColumnBuffer::Binary(values) => values.push(None),
+        ColumnBuffer::Date(values) => values.push(None),
+        ColumnBuffer::Datetime { values, .. } => values.push(None),
+    }
+}
+
+fn to_f64(value: ValueRef<'_>) -> ConversionResult<f64>
+{
+    match value
+    {
+        ValueRef::Integer(v) => Ok(v as f64),
+        ValueRef::Real(v) => Ok(v),
+        _ => Ok(value.as_str()?.trim().parse()?),
+    }
+}
+
+/// Datas no SQLite são texto ISO 8601 (`2024-01-31`, `2024-01-31 13:45:00.123`) ou epoch em segundos.
+fn to_datetime(value: ValueRef<'_>) -> ConversionResult<NaiveDateTime>
+{
+    if let ValueRef::Integer(secs) = value
+    {
+        return DateTime::from_timestamp(secs, 0)
+            .map(|dt| dt.naive_utc())
+            .ok_or_else(|| format!("epoch fora do intervalo: {}", secs).into());
+    }
+
+    let text = value.as_str()?.trim();
+    for format in [
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M",
+    ]
+    {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(text, format)
+        {
+            return Ok(dt);
+        }
+    }
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .map(|d| d.and_time(Default::default()))
+        .map_err(|_| format!("data inválida: {:?}", text).into())
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::oracle::testing::sqlite_engine;
+
+    #[test]
+    fn query_df_types_columns_from_declared_types()
+    {
+        let df = sqlite_engine()
+            .query_df("SELECT * FROM chassi ORDER BY id", &[], &QueryOptions::default(), None)
+            .unwrap();
+
+        let dtypes: Vec<DataType> = df.dtypes();
+        assert_eq!(
+            dtypes,
+            vec![
+                DataType::Int64,
+                DataType::String,
+                DataType::Decimal(Some(10), Some(2)),
+                DataType::Boolean,
+                DataType::Int64,
+                DataType::Datetime(TimeUnit::Milliseconds, None),
+                DataType::Datetime(TimeUnit::Milliseconds, None),
+                DataType::Binary,
+            ]
+        );
+        assert_eq!(df.height(), 5);
+        assert_eq!(df.column("nome").unwrap().str().unwrap().get(1), Some("DEF"));
+        assert_eq!(df.column("nome").unwrap().null_count(), 1);
+        assert_eq!(df.column("ativo").unwrap().bool().unwrap().get(0), Some(true));
+        assert_eq!(df.column("foto").unwrap().binary().unwrap().get(0), Some(&[1u8, 2][..]));
+
+        let alterado = df.column("alterado").unwrap().datetime().unwrap();
+        assert_eq!(alterado.get(0), Some(1706708700123));
+        assert_eq!(alterado.get(1), Some(1706745600000));
+    }
+
+    #[test]
+    fn query_df_applies_binds_and_options()
+    {
+        let options = QueryOptions::new()
+            .date_mapping(DateMapping::Date)
+            .boolean_column("FLAG")
+            .column_type("ID", DataType::String);
+        let df = sqlite_engine()
+            .query_df(
+                "SELECT id, flag, entrada, id * 1.5 AS calc FROM chassi WHERE id = ? AND nome <> ?",
+                &[SqlValue::Int(2), SqlValue::Text("ABC".to_string())],
+                &options,
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(df.height(), 1);
+        assert_eq!(
+            df.dtypes(),
+            vec![DataType::String, DataType::Boolean, DataType::Date, DataType::Float64]
+        );
+        assert_eq!(df.column("id").unwrap().str().unwrap().get(0), Some("2"));
+        assert_eq!(df.column("flag").unwrap().bool().unwrap().get(0), Some(true));
+        assert_eq!(df.column("calc").unwrap().f64().unwrap().get(0), Some(3.0));
+
+        let strings = sqlite_engine()
+            .query_df(
+                "SELECT id, preco FROM chassi WHERE id = 1",
+                &[],
+                &QueryOptions::new().mode(FetchMode::Strings),
+                None,
+            )
+            .unwrap();
+        assert_eq!(strings.dtypes(), vec![DataType::String, DataType::String]);
+        assert_eq!(strings.column("preco").unwrap().str().unwrap().get(0), Some("10.5"));
+    }
+
+    #[test]
+    fn query_df_reports_conversion_and_cancel()
+    {
+        let engine = sqlite_engine();
+        engine
+            .execute(
+                "INSERT INTO chassi (id, entrada) VALUES (?, ?)",
+                &[SqlValue::Int(6), SqlValue::Text("31/01/2024".to_string())],
+            )
+            .unwrap();
+        let error = engine
+            .query_df(
+                "SELECT id, entrada FROM chassi ORDER BY id",
+                &[],
+                &QueryOptions::default(),
+                None,
+            )
+            .unwrap_err();
+        assert!(
+            matches!(&error, EngineError::Conversion { column, row: 6, .. } if column == "entrada"),
+            "{:?}",
+            error
+        );
+
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        let cancelled = engine.query_df("SELECT id FROM chassi", &[], &QueryOptions::default(), Some(&cancel));
+        assert!(matches!(cancelled, Err(EngineError::Cancelled { .. })));
+    }
+
+    #[test]
+    fn query_df_stream_slices_the_result()
+    {
+        let engine = sqlite_engine();
+        let heights: Vec<usize> = engine
+            .query_df_stream("SELECT id FROM chassi ORDER BY id", &[], 2, &QueryOptions::default())
+            .unwrap()
+            .map(|chunk| chunk.unwrap().height())
+            .collect();
+        assert_eq!(heights, vec![2, 2, 1]);
+
+        // Sem linhas: um bloco vazio, que ainda traz as colunas
+        let chunks: Vec<DataFrame> = engine
+            .query_df_stream(
+                "SELECT id, nome FROM chassi WHERE id > ?",
+                &[SqlValue::Int(10)],
+                2,
+                &QueryOptions::default(),
+            )
+            .unwrap()
+            .collect::<EngineResult<_>>()
+            .unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].height(), 0);
+        assert_eq!(chunks[0].get_column_names(), vec!["id", "nome"]);
+
+        let invalid = engine.query_df_stream("SELECT id FROM chassi", &[], 0, &QueryOptions::default());
+        assert!(matches!(invalid, Err(EngineError::InvalidArgument(_))));
+    }
+}
@@ -0,0 +1,326 @@
+use std::time::Duration;
+
+use log::info;
+use oracle::sql_type::ToSql;
+use oracle::Connection;
+use oracle::ResultSet;
+use oracle::Row;
+use polars::prelude::*;
+
+use super::cancel::clear_call_timeout;
+use super::cancel::CancelToken;
+use super::error::EngineError;
+use super::error::EngineResult;
+use super::lob::LobColumn;
+use super::options::QueryOptions;
+use super::pool::EngineConnection;
+use super::types::cast_series;
+use super::types::ColumnBuffer;
+
+/// Prepara e executa a query aplicando o ajuste de fetch das opções.
+pub(crate) fn open_result_set(
+    conn: &Connection,
+    sql: &str,
+    params: &[&dyn ToSql],
+    options: &QueryOptions,
+) -> EngineResult<ResultSet<'static, Row>>
+{
+    let mut builder = conn.statement(sql);
+    if let Some(size) = options.fetch_array_size
+    {
+        builder.fetch_array_size(size);
+    }
+    if let Some(rows) = options.prefetch_rows
+    {
+        builder.prefetch_rows(rows);
+    }
+    // Com o locator, CLOB/BLOB podem ser lidos em partes em vez de inteiros
+    if options.needs_lob_locator()
+    {
+        builder.lob_locator();
+    }
+    let stmt = builder.build()?;
+    Ok(stmt.into_result_set(params)?)
+}
+
+/// Colunas do ResultSet com o tipo Polars de destino e, nas colunas grandes, o leitor do `LobMode`.
+pub(crate) struct ResultSchema
+{
+    pub names: Vec<String>,
+    /// Tipo em que cada coluna é lida.
+    pub dtypes: Vec<DataType>,
+    lobs: Vec<Option<LobColumn>>,
+    /// Tipo forçado para as colunas lidas em outro tipo e convertidas ao fim do bloco.
+    casts: Vec<Option<DataType>>,
+}
+
+impl ResultSchema
+{
+    /// Tipo de cada coluna no DataFrame gerado.
+    pub fn output_dtypes(&self) -> Vec<DataType>
+    {
+        self.dtypes
+            .iter()
+            .zip(&self.casts)
+            .map(|(dtype, cast)| cast.as_ref().unwrap_or(dtype).clone())
+            .collect()
+    }
+}
+
+/// Nomes, tipos Polars de destino e leitores de LOB das colunas do ResultSet.
+pub(crate) fn result_schema(rows: &ResultSet<'_, Row>, options: &QueryOptions) -> ResultSchema
+{
+    schema_with_locator(rows, options, options.needs_lob_locator())
+}
+
+/// Como `result_schema`, para ResultSets abertos sem o statement do engine (REF CURSOR),
+/// em que os LOBs nunca vêm como locator.
+pub(crate) fn cursor_schema(rows: &ResultSet<'_, Row>, options: &QueryOptions) -> ResultSchema
+{
+    schema_with_locator(rows, options, false)
+}
+
+fn schema_with_locator(rows: &ResultSet<'_, Row>, options: &QueryOptions, locator: bool) -> ResultSchema
+{
+    let column_info = rows.column_info();
+    let names: Vec<String> = column_info.iter().map(|ci| ci.name().to_string()).collect();
+    let dtypes: Vec<DataType> = column_info.iter().map(|ci| options.read_dtype_for(ci)).collect();
+    let casts = column_info
+        .iter()
+        .zip(&dtypes)
+        .map(|(ci, dtype)| Some(options.dtype_for(ci)).filter(|target| target != dtype))
+        .collect();
+    let lobs = column_info
+        .iter()
+        .zip(&dtypes)
+        .map(|(ci, dtype)| LobColumn::new(ci.name(), ci.oracle_type(), dtype, options.lob_mode_for(ci.name()), locator))
+        .collect();
+    ResultSchema {
+        names,
+        dtypes,
+        lobs,
+        casts,
+    }
+}
+
+/// Lê até `limit` linhas (ou todas, se `None`) do ResultSet e monta um DataFrame.
+///
+/// `offset` é o número de linhas já lidas antes deste bloco, usado apenas nas mensagens de erro.
+pub(crate) fn fetch_chunk(
+    rows: &mut ResultSet<'_, Row>,
+    schema: &ResultSchema,
+    limit: Option<usize>,
+    offset: usize,
+    cancel: Option<&CancelToken>,
+) -> EngineResult<DataFrame>
+{
+    let mut buffers: Vec<ColumnBuffer> = schema.dtypes.iter().map(ColumnBuffer::new).collect();
+
+    let mut row_count = 0usize;
+    while limit.is_none_or(|limit| row_count < limit)
+    {
+        let Some(row_res) = rows.next()
+        else
+        {
+            break;
+        };
+
+        if cancel.is_some_and(|c| c.is_cancelled())
+        {
+            info!("Consulta cancelada após {} linhas.", offset + row_count);
+            return Err(EngineError::Cancelled {
+                sql_id: None,
+                source: None,
+            });
+        }
+
+        let row: Row = row_res?;
+        let row_no = offset + row_count + 1;
+        for (i, buffer) in buffers.iter_mut().enumerate()
+        {
+            let pushed = match &schema.lobs[i]
+            {
+                Some(lob) => lob.push(buffer, &row, i, row_no),
+                None => buffer.push(&row, i),
+            };
+            pushed.map_err(|e| EngineError::Conversion {
+                column: schema.names[i].clone(),
+                row: row_no,
+                message: e.to_string(),
+                sql_id: None,
+            })?;
+        }
+        row_count += 1;
+    }
+
+    let mut columns = Vec::with_capacity(buffers.len());
+    for (i, buffer) in buffers.into_iter().enumerate()
+    {
+        let mut series = buffer.into_series(schema.names[i].as_str().into())?;
+        if let Some(target) = &schema.casts[i]
+        {
+            series = cast_series(&series, target).map_err(|(idx, message)| EngineError::Conversion {
+                column: schema.names[i].clone(),
+                row: offset + idx + 1,
+                message,
+                sql_id: None,
+            })?;
+        }
+        columns.push(series.into_column());
+    }
+
+    Ok(DataFrame::new(columns)?)
+}
+
+/// Iterador de DataFrames com até `chunk_rows` linhas cada, lidos sob demanda do Oracle.
+///
+/// Mantém a conexão emprestada (do pool, se houver) até ser descartado. Uma consulta sem
+/// linhas produz um único DataFrame vazio, com o schema das colunas.
+pub struct DataFrameStream
+{
+    // `rows` precisa ser descartado antes da conexão
+    rows: ResultSet<'static, Row>,
+    conn: EngineConnection,
+    schema: ResultSchema,
+    chunk_rows: usize,
+    rows_read: usize,
+    chunks_read: usize,
+    done: bool,
+}
+
+impl DataFrameStream
+{
+    pub(crate) fn new(
+        conn: EngineConnection,
+        sql: &str,
+        params: &[&dyn ToSql],
+        chunk_rows: usize,
+        options: &QueryOptions,
+        timeout: Option<Duration>,
+    ) -> EngineResult<Self>
+    {
+        if chunk_rows == 0
+        {
+            return Err(EngineError::InvalidArgument("chunk_rows deve ser maior que zero".to_string()));
+        }
+        // Limita cada round-trip; é removido no Drop, antes da conexão voltar ao pool, ou aqui
+        // mesmo se a consulta falhar, já que sem o stream não há Drop
+        conn.set_call_timeout(timeout)?;
+        let rows = match open_result_set(&conn, sql, params, options)
+        {
+            Ok(rows) => rows,
+            Err(e) =>
+            {
+                clear_call_timeout(&conn);
+                return Err(e);
+            },
+        };
+        let schema = result_schema(&rows, options);
+        info!("Colunas encontradas: {:?}", schema.names);
+
+        Ok(Self {
+            rows,
+            conn,
+            schema,
+            chunk_rows,
+            rows_read: 0,
+            chunks_read: 0,
+            done: false,
+        })
+    }
+
+    /// Nomes das colunas retornadas pela consulta.
+    pub fn column_names(&self) -> &[String]
+    {
+        &self.schema.names
+    }
+
+    /// Total de linhas lidas até o momento.
+    pub fn rows_read(&self) -> usize
+    {
+        self.rows_read
+    }
+}
+
+impl Drop for DataFrameStream
+{
+    fn drop(&mut self)
+    {
+        clear_call_timeout(&self.conn);
+    }
+}
+
+impl Iterator for DataFrameStream
+{
+    type Item = EngineResult<DataFrame>;
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        if self.done
+        {
+            return None;
+        }
+
+        let chunk = match fetch_chunk(&mut self.rows, &self.schema, Some(self.chunk_rows), self.rows_read, None)
+        {
+            Ok(chunk) => chunk,
+            Err(e) =>
+            {
+                self.done = true;
+                return Some(Err(e));
+            },
+        };
+
+        let height = chunk.height();
+        if height < self.chunk_rows
+        {
+            self.done = true;
+            info!("Consulta executada; {} linhas recuperadas.", self.rows_read + height);
+        }
+        if height == 0 && self.chunks_read > 0
+        {
+            return None;
+        }
+
+        self.rows_read += height;
+        self.chunks_read += 1;
+        Some(Ok(chunk))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::oracle::config::OracleConfig;
+    use crate::oracle::connection::EngineOracle;
+    use crate::oracle::pool::PoolConfig;
+
+    #[test]
+    #[ignore = "precisa de um Oracle acessível pelas variáveis ORACLE_* do .env"]
+    fn failed_stream_does_not_leave_the_call_timeout_on_the_session()
+    {
+        dotenv::dotenv().ok();
+        // Pool de uma única sessão: a próxima retirada devolve a sessão da consulta que falhou
+        let config = OracleConfig {
+            pool: Some(PoolConfig {
+                min_connections: 1,
+                max_connections: 1,
+                ..PoolConfig::default()
+            }),
+            ..OracleConfig::from_env().unwrap()
+        };
+        let engine = EngineOracle::from_config(&config).unwrap();
+        let options = QueryOptions {
+            timeout: Some(Duration::from_secs(5)),
+            ..QueryOptions::default()
+        };
+
+        let result = engine.query_to_polars_df_stream("SELECT * FROM TABELA_QUE_NAO_EXISTE", &[], 100, &options);
+        assert!(matches!(result, Err(EngineError::Oracle { code: Some(942), .. })));
+
+        let conn = engine.connection().unwrap();
+        assert_eq!(conn.call_timeout().unwrap(), None);
+        engine.query_to_polars_df("SELECT 1 FROM dual", &[]).unwrap();
+    }
+}
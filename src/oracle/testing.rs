@@ -0,0 +1,79 @@
+//! Apoio comum aos testes dos módulos: o banco SQLite de exemplo e diretórios temporários.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+use super::sqlite::EngineSqlite;
+
+/// Engine SQLite em memória com a tabela `chassi`: cinco linhas, a terceira toda nula (exceto o id).
+pub(crate) fn sqlite_engine() -> EngineSqlite
+{
+    let engine = EngineSqlite::open_in_memory().unwrap();
+    engine
+        .execute_batch(
+            "CREATE TABLE chassi (
+                id INTEGER,
+                nome VARCHAR(20),
+                preco DECIMAL(10, 2),
+                ativo BOOLEAN,
+                flag INTEGER,
+                entrada DATE,
+                alterado DATETIME,
+                foto BLOB
+            );
+            INSERT INTO chassi VALUES (1, 'ABC', 10.5, 1, 0, '2024-01-31', '2024-01-31 13:45:00.123', x'0102');
+            INSERT INTO chassi VALUES (2, 'DEF', 3, 0, 1, '2024-02-01', 1706745600, NULL);
+            INSERT INTO chassi VALUES (3, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+            INSERT INTO chassi VALUES (4, 'GHI', 7.25, 1, 0, '2024-02-02', '2024-02-02 08:00:00', NULL);
+            INSERT INTO chassi VALUES (5, 'JKL', 1, 0, 1, '2024-02-03', '2024-02-03 09:30:00', NULL);",
+        )
+        .unwrap();
+    engine
+}
+
+/// Diretório vazio e exclusivo do teste, removido no drop.
+pub(crate) struct TestDir(PathBuf);
+
+impl TestDir
+{
+    pub(crate) fn new() -> Self
+    {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        let dir = env::temp_dir().join(format!(
+            "rust_concepts_test_{}_{}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+
+    pub(crate) fn path(&self) -> &Path
+    {
+        &self.0
+    }
+
+    /// Nomes dos arquivos do diretório, em ordem.
+    pub(crate) fn file_names(&self) -> Vec<String>
+    {
+        let mut names: Vec<String> = fs::read_dir(&self.0)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+impl Drop for TestDir
+{
+    fn drop(&mut self)
+    {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,153 @@
+use log::info;
+use log::warn;
+use oracle::sql_type::ToSql;
+use oracle::Connection;
+use polars::prelude::DataFrame;
+
+use super::error::EngineError;
+use super::error::EngineResult;
+use super::options::QueryOptions;
+use super::pool::EngineConnection;
+use super::sql::quote_identifier;
+use super::stream::fetch_chunk;
+use super::stream::open_result_set;
+use super::stream::result_schema;
+use super::write::write_df;
+use super::write::WriteMode;
+
+/// Nível de isolamento aplicado com `SET TRANSACTION` no início da transação.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IsolationLevel
+{
+    /// Padrão do Oracle: cada instrução enxerga o que já foi commitado quando ela começou.
+    #[default]
+    ReadCommitted,
+    /// Todas as instruções enxergam o banco como estava no início da transação.
+    Serializable,
+    /// Como `Serializable`, mas recusa qualquer escrita.
+    ReadOnly,
+}
+
+impl IsolationLevel
+{
+    fn statement(self) -> &'static str
+    {
+        match self
+        {
+            IsolationLevel::ReadCommitted => "SET TRANSACTION ISOLATION LEVEL READ COMMITTED",
+            IsolationLevel::Serializable => "SET TRANSACTION ISOLATION LEVEL SERIALIZABLE",
+            IsolationLevel::ReadOnly => "SET TRANSACTION READ ONLY",
+        }
+    }
+}
+
+/// Transação aberta em uma conexão do engine.
+///
+/// Termina com `commit` ou `rollback`; se for descartada antes disso (erro propagado com `?`
+/// ou panic), faz rollback. Com conexão única (sem pool), a transação reserva a sessão até
+/// terminar: chamadas de outras threads ao engine esperam, para não confirmar nem desfazer o
+/// trabalho dela, e chamadas da mesma thread falham; use os métodos da própria transação.
+pub struct Transaction
+{
+    conn: EngineConnection,
+    finished: bool,
+}
+
+impl Transaction
+{
+    pub(crate) fn begin(conn: EngineConnection, isolation: IsolationLevel) -> EngineResult<Self>
+    {
+        if isolation != IsolationLevel::ReadCommitted
+        {
+            conn.execute(isolation.statement(), &[])?;
+        }
+        info!("Transação iniciada ({:?}).", isolation);
+
+        Ok(Self { conn, finished: false })
+    }
+
+    /// Conexão da transação, para operações que o engine não cobre.
+    pub fn connection(&self) -> &Connection
+    {
+        &self.conn
+    }
+
+    /// Executa uma instrução DML e devolve o número de linhas afetadas.
+    pub fn execute(&self, sql: &str, params: &[&dyn ToSql]) -> EngineResult<u64>
+    {
+        let run = || -> EngineResult<u64> { Ok(self.conn.execute(sql, params)?.row_count()?) };
+        run().map_err(|e| e.with_sql(sql))
+    }
+
+    /// Consulta dentro da transação, enxergando as alterações ainda não commitadas.
+    pub fn query_to_polars_df(&self, sql: &str, params: &[&dyn ToSql], options: &QueryOptions) -> EngineResult<DataFrame>
+    {
+        let run = || -> EngineResult<DataFrame> {
+            let mut rows = open_result_set(&self.conn, sql, params, options)?;
+            let schema = result_schema(&rows, options);
+            fetch_chunk(&mut rows, &schema, None, 0, None)
+        };
+        run().map_err(|e| e.with_sql(sql))
+    }
+
+    /// Grava o DataFrame na tabela como parte da transação.
+    ///
+    /// `WriteMode::TruncateInsert` é recusado: o TRUNCATE é DDL e faria commit implícito.
+    pub fn write_polars_df(&self, table: &str, df: &DataFrame, mode: WriteMode) -> EngineResult<usize>
+    {
+        if mode == WriteMode::TruncateInsert
+        {
+            return Err(EngineError::InvalidArgument(
+                "TruncateInsert não pode ser usado dentro de uma transação".to_string(),
+            ));
+        }
+        write_df(&self.conn, table, df, &mode)
+    }
+
+    /// Cria um savepoint com o nome informado.
+    pub fn savepoint(&self, name: &str) -> EngineResult<()>
+    {
+        self.conn.execute(&format!("SAVEPOINT {}", quote_identifier(name)?), &[])?;
+        Ok(())
+    }
+
+    /// Desfaz o que foi feito depois do savepoint, mantendo a transação aberta.
+    pub fn rollback_to(&self, name: &str) -> EngineResult<()>
+    {
+        self.conn
+            .execute(&format!("ROLLBACK TO SAVEPOINT {}", quote_identifier(name)?), &[])?;
+        Ok(())
+    }
+
+    pub fn commit(mut self) -> EngineResult<()>
+    {
+        self.conn.commit()?;
+        self.finished = true;
+        info!("Transação confirmada.");
+        Ok(())
+    }
+
+    pub fn rollback(mut self) -> EngineResult<()>
+    {
+        self.conn.rollback()?;
+        self.finished = true;
+        info!("Transação desfeita.");
+        Ok(())
+    }
+}
+
+impl Drop for Transaction
+{
+    fn drop(&mut self)
+    {
+        if self.finished
+        {
+            return;
+        }
+        warn!("Transação descartada sem commit; executando rollback.");
+        if let Err(e) = self.conn.rollback()
+        {
+            warn!("Falha no rollback da transação: {}", e);
+        }
+    }
+}
@@ -0,0 +1,394 @@
+use chrono::DateTime;
+use chrono::FixedOffset;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use oracle::sql_type::OracleType;
+use oracle::Row;
+use polars::prelude::*;
+
+/// Fuso usado nas colunas `TIMESTAMP WITH (LOCAL) TIME ZONE`; os valores são normalizados para UTC.
+pub const UTC: &str = "UTC";
+
+/// Como o tipo DATE do Oracle (que sempre carrega horário) deve ser representado no Polars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DateMapping
+{
+    /// `Datetime` em milissegundos, preservando o horário.
+    #[default]
+    Datetime,
+    /// `Date`, descartando o horário.
+    Date,
+}
+
+/// Mapeia um tipo Oracle para o `DataType` equivalente do Polars.
+///
+/// - `NUMBER(p, 0)` com até 18 dígitos vira `Int64`; com mais dígitos ou com escala vira `Decimal(p, s)`
+/// - `NUMBER` sem precisão, `FLOAT` e `BINARY_DOUBLE` viram `Float64`
+/// - `DATE` vira `Datetime(ms)` ou `Date`, conforme `date_mapping`
+/// - `TIMESTAMP` vira `Datetime` e `TIMESTAMP WITH TIME ZONE` vira `Datetime` em UTC
+/// - `RAW`, `LONG RAW` e `BLOB` viram `Binary`
+/// - `CLOB`, `NCLOB` e `LONG` viram `String` (veja `LobMode` para limitar ou gravar em arquivo)
+/// - qualquer outro tipo é lido como `String`
+pub fn polars_dtype(oracle_type: &OracleType, date_mapping: DateMapping) -> DataType
+{
+    match oracle_type
+    {
+        // NUMBER sem precisão declarada (ODPI-C informa precisão 0 e escala -127)
+        OracleType::Number(0, -127) => DataType::Float64,
+        OracleType::Number(p, s) if *s <= 0 && *p > 0 && (*p as i16 - *s as i16) <= 18 => DataType::Int64,
+        OracleType::Number(p, s) if *s >= 0 =>
+        {
+            let precision = if *p == 0 { 38 } else { *p as usize };
+            DataType::Decimal(Some(precision), Some(*s as usize))
+        },
+        OracleType::Number(..) | OracleType::Float(_) | OracleType::BinaryDouble => DataType::Float64,
+        OracleType::BinaryFloat => DataType::Float32,
+        OracleType::Int64 => DataType::Int64,
+        OracleType::Boolean => DataType::Boolean,
+        OracleType::Date => match date_mapping
+        {
+            DateMapping::Datetime => DataType::Datetime(TimeUnit::Milliseconds, None),
+            DateMapping::Date => DataType::Date,
+        },
+        OracleType::Timestamp(fsprec) => DataType::Datetime(time_unit(*fsprec), None),
+        OracleType::TimestampTZ(fsprec) | OracleType::TimestampLTZ(fsprec) =>
+        {
+            DataType::Datetime(time_unit(*fsprec), Some(UTC.into()))
+        },
+        OracleType::Raw(_) | OracleType::LongRaw | OracleType::BLOB => DataType::Binary,
+        _ => DataType::String,
+    }
+}
+
+/// Menor unidade do Polars capaz de representar a precisão fracionária do TIMESTAMP.
+fn time_unit(fsprec: u8) -> TimeUnit
+{
+    match fsprec
+    {
+        0..=3 => TimeUnit::Milliseconds,
+        4..=6 => TimeUnit::Microseconds,
+        _ => TimeUnit::Nanoseconds,
+    }
+}
+
+/// Acumula os valores de uma coluna, já no tipo nativo, até a montagem da `Series`.
+pub(crate) enum ColumnBuffer
+{
+    Utf8(Vec<Option<String>>),
+    Int64(Vec<Option<i64>>),
+    Float32(Vec<Option<f32>>),
+    Float64(Vec<Option<f64>>),
+    Decimal
+    {
+        values: Vec<Option<i128>>,
+        precision: usize,
+        scale: usize,
+    },
+    Boolean(Vec<Option<bool>>),
+    Binary(Vec<Option<Vec<u8>>>),
+    Date(Vec<Option<i32>>),
+    Datetime
+    {
+        values: Vec<Option<i64>>,
+        unit: TimeUnit,
+        time_zone: Option<TimeZone>,
+    },
+}
+
+impl ColumnBuffer
+{
+    /// Cria um buffer vazio para o `DataType` de destino. Tipos sem suporte nativo caem em `String`.
+    pub(crate) fn new(dtype: &DataType) -> Self
+    {
+        match dtype
+        {
+            DataType::Int64 => ColumnBuffer::Int64(Vec::new()),
+            DataType::Float32 => ColumnBuffer::Float32(Vec::new()),
+            DataType::Float64 => ColumnBuffer::Float64(Vec::new()),
+            DataType::Decimal(precision, scale) => ColumnBuffer::Decimal {
+                values: Vec::new(),
+                precision: precision.unwrap_or(38),
+                scale: scale.unwrap_or(0),
+            },
+            DataType::Boolean => ColumnBuffer::Boolean(Vec::new()),
+            DataType::Binary => ColumnBuffer::Binary(Vec::new()),
+            DataType::Date => ColumnBuffer::Date(Vec::new()),
+            DataType::Datetime(unit, time_zone) => ColumnBuffer::Datetime {
+                values: Vec::new(),
+                unit: *unit,
+                time_zone: time_zone.clone(),
+            },
+            _ => ColumnBuffer::Utf8(Vec::new()),
+        }
+    }
+
+    /// Lê a coluna `idx` da linha e acrescenta o valor convertido ao buffer.
+    pub(crate) fn push(&mut self, row: &Row, idx: usize) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+    {
+        match self
+        {
+            ColumnBuffer::Utf8(values) => values.push(row.get(idx)?),
+            ColumnBuffer::Int64(values) => values.push(row.get(idx)?),
+            ColumnBuffer::Float32(values) => values.push(row.get(idx)?),
+            ColumnBuffer::Float64(values) => values.push(row.get(idx)?),
+            ColumnBuffer::Decimal { values, scale, .. } =>
+            {
+                let raw: Option<String> = row.get(idx)?;
+                values.push(raw.map(|s| parse_decimal(&s, *scale)).transpose()?);
+            },
+            ColumnBuffer::Boolean(values) =>
+            {
+                let raw: Option<String> = row.get(idx)?;
+                values.push(raw.map(|s| parse_bool(&s)).transpose()?);
+            },
+            ColumnBuffer::Binary(values) => values.push(row.get(idx)?),
+            ColumnBuffer::Date(values) =>
+            {
+                let raw: Option<NaiveDate> = row.get(idx)?;
+                values.push(raw.map(|d| (d - DateTime::UNIX_EPOCH.date_naive()).num_days() as i32));
+            },
+            ColumnBuffer::Datetime { values, unit, time_zone } =>
+            {
+                let raw: Option<NaiveDateTime> = if time_zone.is_some()
+                {
+                    let aware: Option<DateTime<FixedOffset>> = row.get(idx)?;
+                    aware.map(|dt| dt.naive_utc())
+                }
+                else
+                {
+                    row.get(idx)?
+                };
+                values.push(raw.map(|dt| to_timestamp(&dt, *unit)).transpose()?);
+            },
+        }
+        Ok(())
+    }
+
+    /// Converte o buffer em uma `Series` com o nome informado.
+    pub(crate) fn into_series(self, name: PlSmallStr) -> PolarsResult<Series>
+    {
+        let series = match self
+        {
+            ColumnBuffer::Utf8(values) => Series::new(name, values),
+            ColumnBuffer::Int64(values) => Series::new(name, values),
+            ColumnBuffer::Float32(values) => Series::new(name, values),
+            ColumnBuffer::Float64(values) => Series::new(name, values),
+            ColumnBuffer::Decimal {
+                values,
+                precision,
+                scale,
+            } => Int128Chunked::from_iter_options(name, values.into_iter())
+                .into_decimal(Some(precision), scale)?
+                .into_series(),
+            ColumnBuffer::Boolean(values) => Series::new(name, values),
+            ColumnBuffer::Binary(values) => Series::new(name, values),
+            ColumnBuffer::Date(values) => Int32Chunked::from_iter_options(name, values.into_iter())
+                .into_date()
+                .into_series(),
+            ColumnBuffer::Datetime { values, unit, time_zone } => Int64Chunked::from_iter_options(name, values.into_iter())
+                .into_datetime(unit, time_zone)
+                .into_series(),
+        };
+        Ok(series)
+    }
+}
+
+/// Converte a representação textual de um NUMBER em inteiro escalado por `10^scale`.
+pub(crate) fn parse_decimal(text: &str, scale: usize) -> Result<i128, Box<dyn std::error::Error + Send + Sync>>
+{
+    let text = text.trim();
+    let (negative, digits) = match text.strip_prefix('-')
+    {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let (int_part, frac_part) = digits.split_once('.').unwrap_or((digits, ""));
+
+    if (int_part.is_empty() && frac_part.is_empty())
+        || !int_part.bytes().all(|b| b.is_ascii_digit())
+        || !frac_part.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(format!("valor NUMBER inválido para Decimal: {:?}", text).into());
+    }
+
+    // Ajusta a parte fracionária para exatamente `scale` dígitos
+    let mut frac = frac_part.to_string();
+    if frac.len() > scale
+    {
+        frac.truncate(scale);
+    }
+    while frac.len() < scale
+    {
+        frac.push('0');
+    }
+
+    let combined = format!("{}{}", int_part, frac);
+    let value: i128 = if combined.is_empty() { 0 } else { combined.parse()? };
+    Ok(if negative { -value } else { value })
+}
+
+/// Interpreta flags comuns em tabelas PeopleSoft (`Y`/`N`, `S`/`N`, `1`/`0`, `TRUE`/`FALSE`).
+pub(crate) fn parse_bool(text: &str) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>
+{
+    match text.trim().to_ascii_uppercase().as_str()
+    {
+        "1" | "Y" | "S" | "T" | "YES" | "SIM" | "TRUE" => Ok(true),
+        "0" | "N" | "F" | "NO" | "NAO" | "NÃO" | "FALSE" => Ok(false),
+        other => Err(format!("valor não reconhecido como booleano: {:?}", other).into()),
+    }
+}
+
+/// Converte um instante (já em UTC ou sem fuso) para o inteiro usado pelo Polars na unidade dada.
+pub(crate) fn to_timestamp(dt: &NaiveDateTime, unit: TimeUnit) -> Result<i64, Box<dyn std::error::Error + Send + Sync>>
+{
+    let utc = dt.and_utc();
+    match unit
+    {
+        TimeUnit::Milliseconds => Ok(utc.timestamp_millis()),
+        TimeUnit::Microseconds => Ok(utc.timestamp_micros()),
+        TimeUnit::Nanoseconds => utc
+            .timestamp_nanos_opt()
+            .ok_or_else(|| format!("timestamp fora do intervalo suportado em nanossegundos: {}", dt).into()),
+    }
+}
+
+/// Formatos aceitos ao converter texto em `Datetime` por um tipo forçado na consulta.
+const TEXT_DATETIME_FORMATS: &[&str] = &[
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%d/%m/%Y %H:%M:%S",
+    "%d/%m/%Y %H:%M",
+    "%Y%m%d%H%M%S",
+];
+
+/// Formatos aceitos ao converter texto em `Date` (ou `Datetime` à meia-noite).
+const TEXT_DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%d/%m/%Y", "%Y%m%d", "%d-%b-%y", "%d-%b-%Y"];
+
+/// Converte a coluna já lida para o tipo forçado em `QueryOptions::column_types`.
+///
+/// Texto é aparado antes da conversão (colunas `CHAR` vêm com espaços à direita) e vira
+/// `Date`/`Datetime` pelos formatos ISO, `dd/mm/aaaa`, `aaaammdd` e o `DD-MON-RR` do Oracle.
+/// Em caso de falha, devolve a posição (a partir de 0) do primeiro valor não convertido.
+pub(crate) fn cast_series(series: &Series, target: &DataType) -> Result<Series, (usize, String)>
+{
+    if series.dtype() == target
+    {
+        return Ok(series.clone());
+    }
+
+    let fail = |e: PolarsError| (0, e.to_string());
+    let cast = match (series.dtype(), target)
+    {
+        (DataType::String, DataType::Date | DataType::Datetime(..)) =>
+        {
+            text_to_temporal(series.str().map_err(fail)?, target)?
+        },
+        (DataType::String, _) =>
+        {
+            let trimmed: StringChunked = series.str().map_err(fail)?.into_iter().map(|v| v.map(str::trim)).collect();
+            trimmed
+                .with_name(series.name().clone())
+                .into_series()
+                .cast(target)
+                .map_err(fail)?
+        },
+        _ => series.cast(target).map_err(fail)?,
+    };
+
+    // O cast devolve nulo no lugar dos valores que não couberam no tipo; o primeiro deles é o erro
+    let lost = series.is_not_null() & cast.is_null();
+    if let Some(idx) = lost.into_iter().position(|v| v == Some(true))
+    {
+        let value = series.get(idx).map(|v| v.to_string()).unwrap_or_default();
+        return Err((idx, format!("valor {} não pode ser convertido para {}", value, target)));
+    }
+    Ok(cast)
+}
+
+/// Converte texto em `Date` ou `Datetime` (`target`), valor a valor.
+fn text_to_temporal(values: &StringChunked, target: &DataType) -> Result<Series, (usize, String)>
+{
+    let mut parsed = Vec::with_capacity(values.len());
+    for (idx, value) in values.into_iter().enumerate()
+    {
+        let Some(text) = value.map(str::trim).filter(|t| !t.is_empty())
+        else
+        {
+            parsed.push(None);
+            continue;
+        };
+        let dt = parse_text_datetime(text).ok_or_else(|| (idx, format!("texto {:?} não reconhecido como data", text)))?;
+        parsed.push(Some(dt));
+    }
+
+    let name = values.name().clone();
+    let series = match target
+    {
+        DataType::Datetime(unit, time_zone) =>
+        {
+            let timestamps = parsed
+                .iter()
+                .enumerate()
+                .map(|(idx, dt)| {
+                    dt.map(|dt| to_timestamp(&dt, *unit).map_err(|e| (idx, e.to_string())))
+                        .transpose()
+                })
+                .collect::<Result<Vec<Option<i64>>, _>>()?;
+            Int64Chunked::from_iter_options(name, timestamps.into_iter())
+                .into_datetime(*unit, time_zone.clone())
+                .into_series()
+        },
+        _ =>
+        {
+            let days = parsed
+                .iter()
+                .map(|dt| dt.map(|dt| (dt.date() - DateTime::UNIX_EPOCH.date_naive()).num_days() as i32));
+            Int32Chunked::from_iter_options(name, days).into_date().into_series()
+        },
+    };
+    Ok(series)
+}
+
+fn parse_text_datetime(text: &str) -> Option<NaiveDateTime>
+{
+    TEXT_DATETIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+        .or_else(|| {
+            TEXT_DATE_FORMATS
+                .iter()
+                .find_map(|format| NaiveDate::parse_from_str(text, format).ok())
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+}
+
+/// Se o buffer de `dtype` lê a coluna Oracle direto no tipo, sem conversão posterior.
+///
+/// Texto só é lido direto como `String`, `Boolean` ou `Decimal`, que já passam pelo parse
+/// próprio do buffer; os demais tipos são convertidos depois por `cast_series`.
+pub(crate) fn reads_natively(oracle_type: &OracleType, dtype: &DataType) -> bool
+{
+    let native = matches!(
+        dtype,
+        DataType::String
+            | DataType::Int64
+            | DataType::Float32
+            | DataType::Float64
+            | DataType::Decimal(..)
+            | DataType::Boolean
+            | DataType::Binary
+            | DataType::Date
+            | DataType::Datetime(..)
+    );
+    let text = matches!(
+        oracle_type,
+        OracleType::Varchar2(_)
+            | OracleType::NVarchar2(_)
+            | OracleType::Char(_)
+            | OracleType::NChar(_)
+            | OracleType::Long
+            | OracleType::CLOB
+            | OracleType::NCLOB
+    );
+    native && (!text || matches!(dtype, DataType::String | DataType::Boolean | DataType::Decimal(..)))
+}
@@ -0,0 +1,297 @@
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use log::info;
+use oracle::sql_type::OracleType;
+use oracle::sql_type::ToSql;
+use oracle::Connection;
+use polars::prelude::*;
+
+use super::error::EngineError;
+use super::error::EngineResult;
+use super::sql::quote_identifier;
+use super::sql::quote_table_name;
+
+/// Linhas enviadas ao Oracle por execução do array bind.
+pub const WRITE_BATCH_SIZE: usize = 1_000;
+
+/// Como o DataFrame é gravado na tabela de destino.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WriteMode
+{
+    /// Apenas insere as linhas.
+    Append,
+    /// Executa `TRUNCATE TABLE` e depois insere. O TRUNCATE é DDL: não volta atrás em caso de erro.
+    TruncateInsert,
+    /// `MERGE` pelas colunas-chave: atualiza as linhas existentes e insere as novas.
+    Upsert
+    {
+        keys: Vec<String>
+    },
+}
+
+/// Valores de uma coluna já convertidos para tipos Rust aceitos como bind pelo driver.
+enum BindColumn
+{
+    Int(Vec<Option<i64>>),
+    Float(Vec<Option<f64>>),
+    Text(Vec<Option<String>>),
+    Date(Vec<Option<NaiveDate>>),
+    Datetime(Vec<Option<NaiveDateTime>>),
+    Binary(Vec<Option<Vec<u8>>>),
+}
+
+impl BindColumn
+{
+    fn from_series(series: &Series) -> EngineResult<Self>
+    {
+        let column = match series.dtype()
+        {
+            dtype if dtype.is_integer() || dtype.is_bool() =>
+            {
+                BindColumn::Int(series.strict_cast(&DataType::Int64)?.i64()?.iter().collect())
+            },
+            dtype if dtype.is_float() => BindColumn::Float(series.cast(&DataType::Float64)?.f64()?.iter().collect()),
+            DataType::Date => BindColumn::Date(series.date()?.as_date_iter().collect()),
+            DataType::Datetime(..) => BindColumn::Datetime(series.datetime()?.as_datetime_iter().collect()),
+            DataType::Binary => BindColumn::Binary(series.binary()?.iter().map(|v| v.map(<[u8]>::to_vec)).collect()),
+            // Decimal e demais tipos seguem como texto; Decimal é convertido em NUMBER pelo driver
+            _ => BindColumn::Text(
+                series
+                    .cast(&DataType::String)?
+                    .str()?
+                    .iter()
+                    .map(|v| v.map(str::to_string))
+                    .collect(),
+            ),
+        };
+        Ok(column)
+    }
+
+    fn value(&self, row: usize) -> &dyn ToSql
+    {
+        match self
+        {
+            BindColumn::Int(values) => &values[row],
+            BindColumn::Float(values) => &values[row],
+            BindColumn::Text(values) => &values[row],
+            BindColumn::Date(values) => &values[row],
+            BindColumn::Datetime(values) => &values[row],
+            BindColumn::Binary(values) => &values[row],
+        }
+    }
+}
+
+/// Maior VARCHAR2 aceito em bind sem `MAX_STRING_SIZE = EXTENDED`, em bytes.
+const VARCHAR2_MAX_BYTES: usize = 4000;
+
+/// Maior RAW aceito em bind sem `MAX_STRING_SIZE = EXTENDED`, em bytes.
+const RAW_MAX_BYTES: usize = 2000;
+
+/// Tipo Oracle do bind para cada coluna, derivado do dtype do Polars.
+///
+/// Strings e binários usam o maior valor da coluna, em bytes, como tamanho do buffer; acima do
+/// limite de VARCHAR2 (4000 bytes) e de RAW (2000 bytes), a coluna é enviada como CLOB ou BLOB.
+pub fn bind_type(series: &Series) -> EngineResult<OracleType>
+{
+    let oratype = match series.dtype()
+    {
+        dtype if dtype.is_integer() || dtype.is_bool() => OracleType::Int64,
+        dtype if dtype.is_float() => OracleType::BinaryDouble,
+        DataType::Decimal(precision, scale) => OracleType::Number(precision.unwrap_or(38) as u8, scale.unwrap_or(0) as i8),
+        DataType::Date => OracleType::Date,
+        DataType::Datetime(..) => OracleType::Timestamp(9),
+        DataType::Binary => match series.binary()?.iter().flatten().map(<[u8]>::len).max().unwrap_or(1)
+        {
+            max if max > RAW_MAX_BYTES => OracleType::BLOB,
+            max => OracleType::Raw(max.max(1) as u32),
+        },
+        dtype =>
+        {
+            // Só os demais tipos (Categorical, Time, ...) precisam virar texto para medir
+            let max = match dtype
+            {
+                DataType::String => series.str()?.str_len_bytes().max(),
+                _ => series.cast(&DataType::String)?.str()?.str_len_bytes().max(),
+            };
+            match max.unwrap_or(1) as usize
+            {
+                max if max > VARCHAR2_MAX_BYTES => OracleType::CLOB,
+                max => OracleType::Varchar2(max.max(1) as u32),
+            }
+        },
+    };
+    Ok(oratype)
+}
+
+/// Monta a instrução de gravação (INSERT ou MERGE) com binds posicionais.
+pub fn write_sql(table: &str, columns: &[String], mode: &WriteMode) -> EngineResult<String>
+{
+    let table = quote_table_name(table)?;
+    let quoted = columns
+        .iter()
+        .map(|c| quote_identifier(c))
+        .collect::<EngineResult<Vec<String>>>()?;
+
+    let Some(keys) = (match mode
+    {
+        WriteMode::Upsert { keys } => Some(keys),
+        _ => None,
+    })
+    else
+    {
+        let binds: Vec<String> = (1..=quoted.len()).map(|i| format!(":{}", i)).collect();
+        return Ok(format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            table,
+            quoted.join(", "),
+            binds.join(", ")
+        ));
+    };
+
+    if keys.is_empty()
+    {
+        return Err(EngineError::InvalidArgument(
+            "upsert exige ao menos uma coluna-chave".to_string(),
+        ));
+    }
+    let keys = keys
+        .iter()
+        .map(|k| quote_identifier(k))
+        .collect::<EngineResult<Vec<String>>>()?;
+    if let Some(missing) = keys.iter().find(|k| !quoted.contains(k))
+    {
+        return Err(EngineError::InvalidArgument(format!(
+            "coluna-chave {} não existe no DataFrame",
+            missing
+        )));
+    }
+
+    let source: Vec<String> = quoted
+        .iter()
+        .enumerate()
+        .map(|(i, c)| format!(":{} AS {}", i + 1, c))
+        .collect();
+    let on: Vec<String> = keys.iter().map(|k| format!("t.{k} = s.{k}")).collect();
+    let updates: Vec<String> = quoted
+        .iter()
+        .filter(|c| !keys.contains(c))
+        .map(|c| format!("t.{c} = s.{c}"))
+        .collect();
+    let values: Vec<String> = quoted.iter().map(|c| format!("s.{}", c)).collect();
+
+    let mut sql = format!(
+        "MERGE INTO {} t USING (SELECT {} FROM dual) s ON ({})",
+        table,
+        source.join(", "),
+        on.join(" AND ")
+    );
+    if !updates.is_empty()
+    {
+        sql.push_str(&format!(" WHEN MATCHED THEN UPDATE SET {}", updates.join(", ")));
+    }
+    sql.push_str(&format!(
+        " WHEN NOT MATCHED THEN INSERT ({}) VALUES ({})",
+        quoted.join(", "),
+        values.join(", ")
+    ));
+    Ok(sql)
+}
+
+/// Grava o DataFrame na tabela em lotes de `WRITE_BATCH_SIZE` linhas, sem commit.
+///
+/// Retorna o número de linhas enviadas.
+pub(crate) fn write_df(conn: &Connection, table: &str, df: &DataFrame, mode: &WriteMode) -> EngineResult<usize>
+{
+    if df.width() == 0
+    {
+        return Err(EngineError::InvalidArgument("DataFrame sem colunas".to_string()));
+    }
+
+    let columns: Vec<String> = df.get_column_names().iter().map(|c| c.to_string()).collect();
+    let sql = write_sql(table, &columns, mode)?;
+
+    if *mode == WriteMode::TruncateInsert
+    {
+        conn.execute(&format!("TRUNCATE TABLE {}", quote_table_name(table)?), &[])?;
+        info!("Tabela {} truncada.", table);
+    }
+
+    let mut batch = conn.batch(&sql, WRITE_BATCH_SIZE).build()?;
+    for (i, column) in df.get_columns().iter().enumerate()
+    {
+        batch.set_type(i + 1, &bind_type(column.as_materialized_series())?)?;
+    }
+
+    // Converte e envia uma fatia por vez, para não duplicar o DataFrame inteiro na memória
+    let mut offset = 0;
+    while offset < df.height()
+    {
+        let slice = df.slice(offset as i64, WRITE_BATCH_SIZE);
+        let binds = slice
+            .get_columns()
+            .iter()
+            .map(|c| BindColumn::from_series(c.as_materialized_series()))
+            .collect::<EngineResult<Vec<BindColumn>>>()?;
+
+        for row in 0..slice.height()
+        {
+            let values: Vec<&dyn ToSql> = binds.iter().map(|b| b.value(row)).collect();
+            batch.append_row(&values)?;
+        }
+        offset += slice.height();
+    }
+    batch.execute()?;
+
+    info!("{} linhas gravadas em {}.", offset, table);
+    Ok(offset)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn columns(names: &[&str]) -> Vec<String>
+    {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn write_sql_quotes_reserved_column_names()
+    {
+        let sql = write_sql(
+            "stg_cripple",
+            &columns(&["Chassi", "Date", "Level", "Valor Total"]),
+            &WriteMode::Append,
+        )
+        .unwrap();
+        assert_eq!(
+            sql,
+            "INSERT INTO STG_CRIPPLE (CHASSI, \"DATE\", \"LEVEL\", \"Valor Total\") VALUES (:1, :2, :3, :4)"
+        );
+    }
+
+    #[test]
+    fn write_sql_merges_on_reserved_keys()
+    {
+        let mode = WriteMode::Upsert {
+            keys: columns(&["chassi", "date"]),
+        };
+        let sql = write_sql("sysadm.stg", &columns(&["Chassi", "Date", "Size"]), &mode).unwrap();
+        assert_eq!(
+            sql,
+            "MERGE INTO SYSADM.STG t USING (SELECT :1 AS CHASSI, :2 AS \"DATE\", :3 AS \"SIZE\" FROM dual) s \
+             ON (t.CHASSI = s.CHASSI AND t.\"DATE\" = s.\"DATE\") \
+             WHEN MATCHED THEN UPDATE SET t.\"SIZE\" = s.\"SIZE\" \
+             WHEN NOT MATCHED THEN INSERT (CHASSI, \"DATE\", \"SIZE\") VALUES (s.CHASSI, s.\"DATE\", s.\"SIZE\")"
+        );
+
+        let missing = WriteMode::Upsert {
+            keys: columns(&["Order"]),
+        };
+        assert!(matches!(
+            write_sql("stg", &columns(&["Date"]), &missing),
+            Err(EngineError::InvalidArgument(_))
+        ));
+    }
+}